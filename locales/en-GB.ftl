@@ -7,9 +7,12 @@ sounds = Sounds
 images = Images
 rbxm-files = RBXM Files
 ktx-files = KTX Files
+meshes = Meshes
+videos = Videos
 settings = Settings
 about = About
 logs = Logs
+snapshots = Snapshots
 
 # Buttons
 button-delete-this-dir = Delete this directory <Del>
@@ -30,9 +33,20 @@ button-export-logs = Export log to file
 button-copy = Copy <Ctrl+D>
 button-open = Open <Return>
 button-extract-file = Extract <Ctrl+E>
+button-extract-as = Extract as { $format }
+button-extract-type-as = Extract all of this type as { $format }
+button-extract-scripts = Extract scripts
+button-play = Play
+button-pause = Pause
+button-find-similar = Find similar images
+button-show-all = Show all
+button-show-duplicates = Show duplicates
+button-hide-duplicates = Stop showing duplicates
 button-display-image-preview = Display image previews
 button-disable-display-image-preview = Stop displaying image previews
 input-preview-size = Preview size
+input-similar-images-threshold = How different similar images can be
+input-worker-threads = Files read at once (0 uses every core)
 
 # Confirmations
 confirmation-generic-confirmation-title = Confirmation
@@ -65,6 +79,7 @@ new-updates = New updates available
 contributors = Contributors
 dependencies = Dependencies
 behavior = Behavior
+properties = Properties
 
 # Checkboxes
 check-for-updates = Check for updates
@@ -72,8 +87,12 @@ automatically-install-updates = Automatically install updates
 use-alias = Export your renamed filenames
 use-topbar-buttons = Enable toolbar
 refresh-before-extract = Refresh file list before extracting
+detect-duplicates = Detect assets that are cached more than once (slower refreshing)
+extract-unique-only = Only extract one copy of assets that are cached more than once
+watch-cache = Update the list while Roblox is running
 download-development-build = Use development builds to get the latest features early (These builds may be unstable)
 checkbox-hide-user-logs = Hide username from logs
+animate-previews = Play animated image previews
 
 
 # Descriptions
@@ -85,6 +104,36 @@ swap-choose-file = Double click a file to swap
 swap-with = Double click a file to swap with "{ $asset }"
 logs-description = The logs show how the program is performing, if any errors happen, they will show up here
 copy-choose-file = Double click a file to copy
+similar-to = Images similar to "{ $asset }"
+similar-distance = { $distance } bits different
+duplicates = Assets that are cached more than once
+no-duplicates = No asset is cached more than once
+duplicate-copies = { $copies } copies
+badge-new = New
+button-start-capture = Start capture
+button-stop-capture = Stop capture
+button-export-capture = Export captured assets
+capturing-since = Capturing assets downloaded since { $time }
+captured-assets = Assets added or changed during the capture
+no-captured-assets = No assets were added or changed during the capture
+capture-started = Capture started, play the game and stop the capture when you are done
+capture-stopped = Capture stopped
+snapshots-description = A snapshot remembers every asset in the cache, compare two of them to see which assets were added, removed or modified in between
+input-snapshot-name = Snapshot name
+button-save-snapshot = Save snapshot
+button-compare-snapshots = Compare
+button-delete-snapshot = Delete { $name }
+compare-snapshots = Compare snapshots
+no-snapshots = No snapshots were saved yet
+snapshot-old = From
+snapshot-new = to
+snapshots-identical = The snapshots have the same assets
+snapshot-category-changes = { $category }: { $added } added, { $removed } removed, { $modified } modified
+snapshot-added = Added
+snapshot-removed = Removed
+snapshot-modified = Modified
+reading-snapshot = Reading the cache for the snapshot ({ $item }/{ $total })
+snapshot-saved = Saved the snapshot { $name }
 overwrite-with = Double click a file to overwrite with "{ $asset }"
 
 
@@ -95,6 +144,12 @@ reading-files = Reading files ({ $item }/{ $total })
 extracting-files = Extracting files ({ $item }/{ $total })
 filtering-files = Filtering files ({ $item }/{ $total })
 all-extracted = All files extracted
+extracting-scripts = Extracting scripts ({ $item }/{ $total })
+all-scripts-extracted = Extracted { $count } scripts
+hashing-images = Comparing images ({ $item }/{ $total })
+checking-duplicates = Checking for duplicates ({ $item }/{ $total })
+similar-images-found = Found { $count } similar images
+task-already-running = Another task is still running, try again when it has finished
 stage = Stage { $stage }/{ $max }: { $status }
 swapped = Swapped { $item_a } with { $item_b }
 copied = Overwritten { $item_b } with { $item_a }
@@ -104,7 +159,12 @@ failed-deleting-file = ERROR: Failed to delete ({ $item }/{ $total })
 failed-opening-file = ERROR: Failed to open file
 failed-not-file = ERROR: '{ $file }' Not a file
 error-extracting-file = ERROR: Failed to extract: { $error }
+error-converting-file = ERROR: Failed to convert: { $error }
 error-check-logs = ERROR: Check logs for more details.
+error-reading-model = ERROR: Failed to read model: { $error }
+error-reading-image = ERROR: Failed to read image: { $error }
+error-decoding-sound = ERROR: Failed to decode sound: { $error }
+error-audio-output = Sounds can't be heard as there is no audio output, check logs for more details.
 
 # Misc
 no-function = (Not functional yet)
@@ -115,4 +175,15 @@ download-update-question = Would you like to download the update?
 update-changelog = Update changelog below
 support-sponsor = ♥ Sponsor
 support-project-donate = ♥ Donate
+image-dimensions = { $width } × { $height } pixels
+animation-frames = { $frames } frames, { $duration } seconds
+image-format = Pixel format: { $format }
+image-alpha = Has an alpha channel
+image-opaque = No alpha channel
+sort-default = Sort by: Default
+sort-name = Sort by: Name
+sort-size = Sort by: Size
+sort-modified = Sort by: Last modified
+sort-dimensions = Sort by: Dimensions
+sort-duration = Sort by: Duration
 setting-below-restart-required = Note: Changing the setting below requires restarting the program for it to apply.
\ No newline at end of file
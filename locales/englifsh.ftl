@@ -3,6 +3,9 @@
 language-name = englifsh
 
 # Tabs
+snapshots = Snapshots # TODO: Translate
+meshes = Meshes # TODO: Translate
+videos = Videos # TODO: Translate
 music = msuic
 sounds = osduns
 images = iamgesz
@@ -13,6 +16,17 @@ about = aobut
 logs = loges
 
 # Buttons
+input-worker-threads = Files read at once (0 uses every core) # TODO: Translate
+button-hide-duplicates = Stop showing duplicates # TODO: Translate
+button-show-duplicates = Show duplicates # TODO: Translate
+button-show-all = Show all # TODO: Translate
+button-find-similar = Find similar images # TODO: Translate
+input-similar-images-threshold = How different similar images can be # TODO: Translate
+button-pause = Pause # TODO: Translate
+button-play = Play # TODO: Translate
+button-extract-scripts = Extract scripts # TODO: Translate
+button-extract-type-as = Extract all of this type as { $format } # TODO: Translate
+button-extract-as = Extract as { $format } # TODO: Translate
 button-delete-this-dir = deltlets thsi disrectory <Del>
 button-extract-type = extreact all wityh tshbis type <F3>
 button-refresh = recrtfesh <F5>
@@ -59,6 +73,7 @@ error-invalid-directory-description = Please make sure the path you provided is
 generic-error-critical = cirtical error
 
 # Headings
+properties = Properties # TODO: Translate
 actions = atcions
 updates = udopates
 language-settings = languesage settings
@@ -68,6 +83,10 @@ dependencies = depenceinses
 behavior = beahvior
 
 # Checkboxes
+watch-cache = Update the list while Roblox is running # TODO: Translate
+detect-duplicates = Detect assets that are cached more than once (slower refreshing) # TODO: Translate
+extract-unique-only = Only extract one copy of assets that are cached more than once # TODO: Translate
+animate-previews = Play animated image previews # TODO: Translate
 check-for-updates = chek ofr udopates
 automatically-install-updates = autyom,atically insatll upodates
 use-alias = epxort your rneamved filesnmaes
@@ -78,6 +97,36 @@ checkbox-hide-user-logs = hidies uder5tname from lgo
 
 
 # Descriptions
+button-save-snapshot = Save snapshot # TODO: Translate
+snapshot-saved = Saved the snapshot { $name } # TODO: Translate
+snapshots-identical = The snapshots have the same assets # TODO: Translate
+snapshot-added = Added # TODO: Translate
+snapshot-category-changes = { $category }: { $added } added, { $removed } removed, { $modified } modified # TODO: Translate
+compare-snapshots = Compare snapshots # TODO: Translate
+button-compare-snapshots = Compare # TODO: Translate
+snapshot-new = to # TODO: Translate
+reading-snapshot = Reading the cache for the snapshot ({ $item }/{ $total }) # TODO: Translate
+no-snapshots = No snapshots were saved yet # TODO: Translate
+snapshot-modified = Modified # TODO: Translate
+snapshot-old = From # TODO: Translate
+input-snapshot-name = Snapshot name # TODO: Translate
+snapshot-removed = Removed # TODO: Translate
+button-delete-snapshot = Delete { $name } # TODO: Translate
+snapshots-description = A snapshot remembers every asset in the cache, compare two of them to see which assets were added, removed or modified in between # TODO: Translate
+button-export-capture = Export captured assets # TODO: Translate
+no-captured-assets = No assets were added or changed during the capture # TODO: Translate
+button-stop-capture = Stop capture # TODO: Translate
+button-start-capture = Start capture # TODO: Translate
+capture-started = Capture started, play the game and stop the capture when you are done # TODO: Translate
+capture-stopped = Capture stopped # TODO: Translate
+captured-assets = Assets added or changed during the capture # TODO: Translate
+capturing-since = Capturing assets downloaded since { $time } # TODO: Translate
+badge-new = New # TODO: Translate
+duplicates = Assets that are cached more than once # TODO: Translate
+no-duplicates = No asset is cached more than once # TODO: Translate
+duplicate-copies = { $copies } copies # TODO: Translate
+similar-distance = { $distance } bits different # TODO: Translate
+similar-to = Images similar to "{ $asset }" # TODO: Translate
 clear-cache-description = ife itl is tlanging too lgin to lsit file sm foirmm at edirectory, youc anc lear tyouyr bolxoc ache sto help,t ehc ;leitn  will regerneate this files whern  needed
 extract-all-description = tehbeutotn wiull extadct all assets to folder
 custom-cache-dir-description = if youw atyn taccves tto a difnerfent cache wcahnv e this bleow
@@ -90,6 +139,12 @@ overwrite-with = double clcikca f eile to voerwitte with "{ $asset }"
 
 
 # Statuses
+task-already-running = Another task is still running, try again when it has finished # TODO: Translate
+checking-duplicates = Checking for duplicates ({ $item }/{ $total }) # TODO: Translate
+similar-images-found = Found { $count } similar images # TODO: Translate
+hashing-images = Comparing images ({ $item }/{ $total }) # TODO: Translate
+extracting-scripts = Extracting scripts ({ $item }/{ $total }) # TODO: Translate
+all-scripts-extracted = Extracted { $count } scripts # TODO: Translate
 idling = idlignm
 deleting-files = deltinyg files ({ $item }/{ $total })
 reading-files = dearfding fikles ({ $item }/{ $total })
@@ -101,6 +156,17 @@ swapped = swaopped { $item_a } with { $item_b }
 copied = overwriten { $item_b } with { $item_a }
 
 # Misc
+sort-name = Sort by: Name # TODO: Translate
+sort-dimensions = Sort by: Dimensions # TODO: Translate
+sort-duration = Sort by: Duration # TODO: Translate
+image-opaque = No alpha channel # TODO: Translate
+sort-size = Sort by: Size # TODO: Translate
+sort-modified = Sort by: Last modified # TODO: Translate
+sort-default = Sort by: Default # TODO: Translate
+image-format = Pixel format: { $format } # TODO: Translate
+image-alpha = Has an alpha channel # TODO: Translate
+image-dimensions = { $width } × { $height } pixels # TODO: Translate
+animation-frames = { $frames } frames, { $duration } seconds # TODO: Translate
 no-function = (Not functional yet)
 version = vwrisoon: v{ $version } (omcpleid at { $date })
 cache-directory = cache idrectory: { $directory }
@@ -114,4 +180,9 @@ failed-deleting-file = erroro: fialed ot dellte ({ $item }/{ $total })
 failed-opening-file = error: fiaeldw to wopen fiel
 error-extracting-file = rror: fialed to aextract beucase { $error }
 error-check-logs = eroror hcekc logs
-failed-not-file = error '{ $file }' not a fiele
\ No newline at end of file
+failed-not-file = error '{ $file }' not a fiele
+error-reading-model = ERROR: Failed to read model: { $error } # TODO: Translate
+error-reading-image = ERROR: Failed to read image: { $error } # TODO: Translate
+error-decoding-sound = ERROR: Failed to decode sound: { $error } # TODO: Translate
+error-audio-output = Sounds can't be heard as there is no audio output, check logs for more details. # TODO: Translate
+error-converting-file = ERROR: Failed to convert: { $error } # TODO: Translate
\ No newline at end of file
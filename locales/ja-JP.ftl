@@ -114,4 +114,75 @@ button-disable-display-image-preview = Stop displaying image previews # TODO: Tr
 generic-error-critical = Critical error # TODO: Translate
 download-development-build = Use development builds to get the latest features early (These builds may be unstable) # TODO: Translate
 setting-below-restart-required = Note: Changing the setting below requires restarting the program for it to apply. # TODO: Translate
-checkbox-hide-user-logs = Hide username from logs # TODO: Translate
\ No newline at end of file
+checkbox-hide-user-logs = Hide username from logs # TODO: Translate
+videos = Videos # TODO: Translate
+button-extract-as = Extract as { $format } # TODO: Translate
+properties = Properties # TODO: Translate
+error-reading-model = ERROR: Failed to read model: { $error } # TODO: Translate
+button-extract-type-as = Extract all of this type as { $format } # TODO: Translate
+all-scripts-extracted = Extracted { $count } scripts # TODO: Translate
+button-extract-scripts = Extract scripts # TODO: Translate
+extracting-scripts = Extracting scripts ({ $item }/{ $total }) # TODO: Translate
+meshes = Meshes # TODO: Translate
+error-reading-image = ERROR: Failed to read image: { $error } # TODO: Translate
+animation-frames = { $frames } frames, { $duration } seconds # TODO: Translate
+animate-previews = Play animated image previews # TODO: Translate
+image-dimensions = { $width } × { $height } pixels # TODO: Translate
+error-decoding-sound = ERROR: Failed to decode sound: { $error } # TODO: Translate
+error-audio-output = Sounds can't be heard as there is no audio output, check logs for more details. # TODO: Translate
+button-play = Play # TODO: Translate
+button-pause = Pause # TODO: Translate
+image-alpha = Has an alpha channel # TODO: Translate
+image-format = Pixel format: { $format } # TODO: Translate
+sort-default = Sort by: Default # TODO: Translate
+sort-modified = Sort by: Last modified # TODO: Translate
+sort-size = Sort by: Size # TODO: Translate
+image-opaque = No alpha channel # TODO: Translate
+sort-duration = Sort by: Duration # TODO: Translate
+sort-dimensions = Sort by: Dimensions # TODO: Translate
+sort-name = Sort by: Name # TODO: Translate
+input-similar-images-threshold = How different similar images can be # TODO: Translate
+hashing-images = Comparing images ({ $item }/{ $total }) # TODO: Translate
+similar-to = Images similar to "{ $asset }" # TODO: Translate
+similar-distance = { $distance } bits different # TODO: Translate
+similar-images-found = Found { $count } similar images # TODO: Translate
+button-find-similar = Find similar images # TODO: Translate
+button-show-all = Show all # TODO: Translate
+duplicate-copies = { $copies } copies # TODO: Translate
+extract-unique-only = Only extract one copy of assets that are cached more than once # TODO: Translate
+detect-duplicates = Detect assets that are cached more than once (slower refreshing) # TODO: Translate
+no-duplicates = No asset is cached more than once # TODO: Translate
+duplicates = Assets that are cached more than once # TODO: Translate
+button-show-duplicates = Show duplicates # TODO: Translate
+button-hide-duplicates = Stop showing duplicates # TODO: Translate
+checking-duplicates = Checking for duplicates ({ $item }/{ $total }) # TODO: Translate
+input-worker-threads = Files read at once (0 uses every core) # TODO: Translate
+watch-cache = Update the list while Roblox is running # TODO: Translate
+badge-new = New # TODO: Translate
+capturing-since = Capturing assets downloaded since { $time } # TODO: Translate
+captured-assets = Assets added or changed during the capture # TODO: Translate
+capture-stopped = Capture stopped # TODO: Translate
+capture-started = Capture started, play the game and stop the capture when you are done # TODO: Translate
+button-start-capture = Start capture # TODO: Translate
+button-stop-capture = Stop capture # TODO: Translate
+no-captured-assets = No assets were added or changed during the capture # TODO: Translate
+button-export-capture = Export captured assets # TODO: Translate
+snapshots = Snapshots # TODO: Translate
+snapshots-description = A snapshot remembers every asset in the cache, compare two of them to see which assets were added, removed or modified in between # TODO: Translate
+button-delete-snapshot = Delete { $name } # TODO: Translate
+snapshot-removed = Removed # TODO: Translate
+input-snapshot-name = Snapshot name # TODO: Translate
+snapshot-old = From # TODO: Translate
+snapshot-modified = Modified # TODO: Translate
+no-snapshots = No snapshots were saved yet # TODO: Translate
+reading-snapshot = Reading the cache for the snapshot ({ $item }/{ $total }) # TODO: Translate
+snapshot-new = to # TODO: Translate
+button-compare-snapshots = Compare # TODO: Translate
+compare-snapshots = Compare snapshots # TODO: Translate
+snapshot-category-changes = { $category }: { $added } added, { $removed } removed, { $modified } modified # TODO: Translate
+snapshot-added = Added # TODO: Translate
+snapshots-identical = The snapshots have the same assets # TODO: Translate
+snapshot-saved = Saved the snapshot { $name } # TODO: Translate
+button-save-snapshot = Save snapshot # TODO: Translate
+error-converting-file = ERROR: Failed to convert: { $error } # TODO: Translate
+task-already-running = Another task is still running, try again when it has finished # TODO: Translate
\ No newline at end of file
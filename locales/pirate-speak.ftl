@@ -3,6 +3,9 @@
 language-name = Pirate Speak  
 
 # Tabs  
+snapshots = Snapshots # TODO: Translate
+meshes = Meshes # TODO: Translate
+videos = Videos # TODO: Translate
 music = Sea Shanties  
 sounds = Cannon Blasts  
 images = Treasure Maps  
@@ -13,6 +16,17 @@ about = 'Bout This Vessel
 logs = Ship’s Log  
 
 # Buttons  
+input-worker-threads = Files read at once (0 uses every core) # TODO: Translate
+button-hide-duplicates = Stop showing duplicates # TODO: Translate
+button-show-duplicates = Show duplicates # TODO: Translate
+button-show-all = Show all # TODO: Translate
+button-find-similar = Find similar images # TODO: Translate
+input-similar-images-threshold = How different similar images can be # TODO: Translate
+button-pause = Pause # TODO: Translate
+button-play = Play # TODO: Translate
+button-extract-scripts = Extract scripts # TODO: Translate
+button-extract-type-as = Extract all of this type as { $format } # TODO: Translate
+button-extract-as = Extract as { $format } # TODO: Translate
 button-delete-this-dir = Scuttle This Hold <Del>  
 button-extract-type = Plunder All o’ This Kind <F3>  
 button-refresh = Hoist the Colors <F5>  
@@ -59,6 +73,7 @@ error-invalid-directory-description = Avast! The path ye set ain’t leadin’ t
 generic-error-critical = A Most Dire Curse!  
 
 # Headings  
+properties = Properties # TODO: Translate
 actions = Orders  
 updates = Messages in a Bottle  
 language-settings = Speak Like a Pirate  
@@ -68,6 +83,10 @@ dependencies = Ship’s Riggings
 behavior = How the Crew Acts  
 
 # Checkboxes  
+watch-cache = Update the list while Roblox is running # TODO: Translate
+detect-duplicates = Detect assets that are cached more than once (slower refreshing) # TODO: Translate
+extract-unique-only = Only extract one copy of assets that are cached more than once # TODO: Translate
+animate-previews = Play animated image previews # TODO: Translate
 check-for-updates = Keep an Eye on the Horizon  
 automatically-install-updates = Let the Crew Handle Updates  
 use-alias = Mark Ye Plunder with Custom Names  
@@ -77,6 +96,36 @@ download-development-build = Test New Gadgets (May Be Unstable Waters)
 checkbox-hide-user-logs = Hide Ye True Name from the Ship’s Log  
 
 # Descriptions  
+button-save-snapshot = Save snapshot # TODO: Translate
+snapshot-saved = Saved the snapshot { $name } # TODO: Translate
+snapshots-identical = The snapshots have the same assets # TODO: Translate
+snapshot-added = Added # TODO: Translate
+snapshot-category-changes = { $category }: { $added } added, { $removed } removed, { $modified } modified # TODO: Translate
+compare-snapshots = Compare snapshots # TODO: Translate
+button-compare-snapshots = Compare # TODO: Translate
+snapshot-new = to # TODO: Translate
+reading-snapshot = Reading the cache for the snapshot ({ $item }/{ $total }) # TODO: Translate
+no-snapshots = No snapshots were saved yet # TODO: Translate
+snapshot-modified = Modified # TODO: Translate
+snapshot-old = From # TODO: Translate
+input-snapshot-name = Snapshot name # TODO: Translate
+snapshot-removed = Removed # TODO: Translate
+button-delete-snapshot = Delete { $name } # TODO: Translate
+snapshots-description = A snapshot remembers every asset in the cache, compare two of them to see which assets were added, removed or modified in between # TODO: Translate
+button-export-capture = Export captured assets # TODO: Translate
+no-captured-assets = No assets were added or changed during the capture # TODO: Translate
+button-stop-capture = Stop capture # TODO: Translate
+button-start-capture = Start capture # TODO: Translate
+capture-started = Capture started, play the game and stop the capture when you are done # TODO: Translate
+capture-stopped = Capture stopped # TODO: Translate
+captured-assets = Assets added or changed during the capture # TODO: Translate
+capturing-since = Capturing assets downloaded since { $time } # TODO: Translate
+badge-new = New # TODO: Translate
+duplicates = Assets that are cached more than once # TODO: Translate
+no-duplicates = No asset is cached more than once # TODO: Translate
+duplicate-copies = { $copies } copies # TODO: Translate
+similar-distance = { $distance } bits different # TODO: Translate
+similar-to = Images similar to "{ $asset }" # TODO: Translate
 clear-cache-description = If it be takin’ too long to find treasure, ye can toss all cargo overboard. The ship will fetch new supplies next time ye sail.  
 extract-all-description = This button will haul all loot aboard and sort it into proper holds, like /cannon-blasts and /treasure-maps. Pick yer stash spot when ye begin.  
 custom-cache-dir-description = If ye be needin’ to find a different stash, set yer course below. Ye can return to the usual waters with the other button. This ain't yer main install.  
@@ -87,7 +136,13 @@ logs-description = The ship’s log keeps track o’ what’s happenin’. If tr
 copy-choose-file = Double-click a file to copy it  
 overwrite-with = Double-click a file to overwrite with "{ $asset }"  
 
-# Statuses  
+# Statuses
+task-already-running = Another task is still running, try again when it has finished # TODO: Translate  
+checking-duplicates = Checking for duplicates ({ $item }/{ $total }) # TODO: Translate
+similar-images-found = Found { $count } similar images # TODO: Translate
+hashing-images = Comparing images ({ $item }/{ $total }) # TODO: Translate
+extracting-scripts = Extracting scripts ({ $item }/{ $total }) # TODO: Translate
+all-scripts-extracted = Extracted { $count } scripts # TODO: Translate
 idling = Swayin’ in the Breeze  
 deleting-files = Tossin’ Files Overboard ({ $item }/{ $total })  
 reading-files = Readin’ the Treasure Maps ({ $item }/{ $total })  
@@ -98,7 +153,12 @@ stage = Stage { $stage }/{ $max }: { $status }
 swapped = Traded { $item_a } for { $item_b }  
 copied = Overwritten { $item_b } with { $item_a }  
 
-# Error Statuses  
+# Error Statuses
+error-converting-file = ERROR: Failed to convert: { $error } # TODO: Translate  
+error-audio-output = Sounds can't be heard as there is no audio output, check logs for more details. # TODO: Translate
+error-decoding-sound = ERROR: Failed to decode sound: { $error } # TODO: Translate
+error-reading-image = ERROR: Failed to read image: { $error } # TODO: Translate
+error-reading-model = ERROR: Failed to read model: { $error } # TODO: Translate
 failed-deleting-file = ERROR: Couldn’t Toss ({ $item }/{ $total }) Overboard!  
 failed-opening-file = ERROR: Couldn’t Crack Open the Chest: { $error }  
 failed-not-file = ERROR: '{ $file }' Ain’t Proper Booty!  
@@ -106,6 +166,17 @@ error-extracting-file = ERROR: Couldn’t Haul It Aboard: { $error }
 error-check-logs = ERROR: Check the Captain’s Log for More Clues.  
 
 # Misc  
+sort-name = Sort by: Name # TODO: Translate
+sort-dimensions = Sort by: Dimensions # TODO: Translate
+sort-duration = Sort by: Duration # TODO: Translate
+image-opaque = No alpha channel # TODO: Translate
+sort-size = Sort by: Size # TODO: Translate
+sort-modified = Sort by: Last modified # TODO: Translate
+sort-default = Sort by: Default # TODO: Translate
+image-format = Pixel format: { $format } # TODO: Translate
+image-alpha = Has an alpha channel # TODO: Translate
+image-dimensions = { $width } × { $height } pixels # TODO: Translate
+animation-frames = { $frames } frames, { $duration } seconds # TODO: Translate
 no-function = (Not Workin’ Yet, Matey)  
 version = Ship’s Version: v{ $version } (Forged on { $date })  
 cache-directory = Treasure Hold: { $directory }  
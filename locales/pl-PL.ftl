@@ -2,6 +2,9 @@
 language-name = Polski
 
 # Tabs
+snapshots = Snapshots # TODO: Translate
+meshes = Meshes # TODO: Translate
+videos = Videos # TODO: Translate
 logs = Dzienniki
 music = Muzyka
 sounds = Dźwięki
@@ -12,6 +15,17 @@ settings = Ustawienia
 about = Informacje
 
 # Buttons
+input-worker-threads = Files read at once (0 uses every core) # TODO: Translate
+button-hide-duplicates = Stop showing duplicates # TODO: Translate
+button-show-duplicates = Show duplicates # TODO: Translate
+button-show-all = Show all # TODO: Translate
+button-find-similar = Find similar images # TODO: Translate
+input-similar-images-threshold = How different similar images can be # TODO: Translate
+button-pause = Pause # TODO: Translate
+button-play = Play # TODO: Translate
+button-extract-scripts = Extract scripts # TODO: Translate
+button-extract-type-as = Extract all of this type as { $format } # TODO: Translate
+button-extract-as = Extract as { $format } # TODO: Translate
 button-disable-display-image-preview = Przestań wyświetlać podgląd obrazu
 button-display-image-preview = Wyświetl podgląd obrazu
 input-preview-size = Rozmiar podglądu
@@ -58,6 +72,7 @@ error-invalid-directory-title = Nieprawidłowy katalog
 error-invalid-directory-description = Upewnij się, że ścieżka którą podałeś(aś) jest katalogiem
 
 # Headings
+properties = Properties # TODO: Translate
 actions = Akcje
 updates = Aktualizacje
 language-settings = Ustawienia języka
@@ -67,6 +82,10 @@ dependencies = Zależności
 behavior = Zachowanie
 
 # Checkboxes
+watch-cache = Update the list while Roblox is running # TODO: Translate
+detect-duplicates = Detect assets that are cached more than once (slower refreshing) # TODO: Translate
+extract-unique-only = Only extract one copy of assets that are cached more than once # TODO: Translate
+animate-previews = Play animated image previews # TODO: Translate
 checkbox-hide-user-logs = Hide username from logs # TODO: Translate
 download-development-build = Use development builds to get the latest features early (These builds may be unstable) # TODO: Translate
 refresh-before-extract = Odśwież listę plików przed wyodrębnieniem
@@ -77,6 +96,36 @@ use-alias = Eksportuj alternatywne nazwy plików
 
 
 # Descriptions
+button-save-snapshot = Save snapshot # TODO: Translate
+snapshot-saved = Saved the snapshot { $name } # TODO: Translate
+snapshots-identical = The snapshots have the same assets # TODO: Translate
+snapshot-added = Added # TODO: Translate
+snapshot-category-changes = { $category }: { $added } added, { $removed } removed, { $modified } modified # TODO: Translate
+compare-snapshots = Compare snapshots # TODO: Translate
+button-compare-snapshots = Compare # TODO: Translate
+snapshot-new = to # TODO: Translate
+reading-snapshot = Reading the cache for the snapshot ({ $item }/{ $total }) # TODO: Translate
+no-snapshots = No snapshots were saved yet # TODO: Translate
+snapshot-modified = Modified # TODO: Translate
+snapshot-old = From # TODO: Translate
+input-snapshot-name = Snapshot name # TODO: Translate
+snapshot-removed = Removed # TODO: Translate
+button-delete-snapshot = Delete { $name } # TODO: Translate
+snapshots-description = A snapshot remembers every asset in the cache, compare two of them to see which assets were added, removed or modified in between # TODO: Translate
+button-export-capture = Export captured assets # TODO: Translate
+no-captured-assets = No assets were added or changed during the capture # TODO: Translate
+button-stop-capture = Stop capture # TODO: Translate
+button-start-capture = Start capture # TODO: Translate
+capture-started = Capture started, play the game and stop the capture when you are done # TODO: Translate
+capture-stopped = Capture stopped # TODO: Translate
+captured-assets = Assets added or changed during the capture # TODO: Translate
+capturing-since = Capturing assets downloaded since { $time } # TODO: Translate
+badge-new = New # TODO: Translate
+duplicates = Assets that are cached more than once # TODO: Translate
+no-duplicates = No asset is cached more than once # TODO: Translate
+duplicate-copies = { $copies } copies # TODO: Translate
+similar-distance = { $distance } bits different # TODO: Translate
+similar-to = Images similar to "{ $asset }" # TODO: Translate
 overwrite-with = Podwójnie kliknij plik aby nadpisać z "{ $asset }"
 copy-choose-file = Podwójnie kliknij plik do skopiowania.
 logs-description = Dziennik pokazuję wydajność programu. Jeżeli występują jakieś błędy, tutaj one się pojawią.
@@ -88,6 +137,12 @@ custom-cache-dir-description = Jeżeli potrzebujesz dostęp do pamięci potrzebn
 use-alias-description = Zamiast eksportowania zwykłej nazwy dla zasobu, zaznaczenie tej opcji wyeksportuje zasób z inną nazwą pliku. Możesz zrobić to w tej aplikacji.
 
 # Statuses
+task-already-running = Another task is still running, try again when it has finished # TODO: Translate
+checking-duplicates = Checking for duplicates ({ $item }/{ $total }) # TODO: Translate
+similar-images-found = Found { $count } similar images # TODO: Translate
+hashing-images = Comparing images ({ $item }/{ $total }) # TODO: Translate
+extracting-scripts = Extracting scripts ({ $item }/{ $total }) # TODO: Translate
+all-scripts-extracted = Extracted { $count } scripts # TODO: Translate
 swapped = Zamieniono { $item_a } z { $item_b }
 copied = Nadpisano { $item_b } z { $item_a }
 idling = Czuwanie
@@ -99,6 +154,11 @@ all-extracted = Wszystkie pliki zostały wyodrębnione.
 stage = Etap { $stage }/{ $max }: { $status }
 
 # Error Statuses
+error-converting-file = ERROR: Failed to convert: { $error } # TODO: Translate
+error-audio-output = Sounds can't be heard as there is no audio output, check logs for more details. # TODO: Translate
+error-decoding-sound = ERROR: Failed to decode sound: { $error } # TODO: Translate
+error-reading-image = ERROR: Failed to read image: { $error } # TODO: Translate
+error-reading-model = ERROR: Failed to read model: { $error } # TODO: Translate
 failed-deleting-file = BŁĄD: Nie udało się usunąć ({ $item }/{ $total })
 failed-opening-file = BŁĄD: Nie udało się otworzyć pliku: { $error }
 failed-not-file = BŁĄD: '{ $file }' Nie jest plikiem.
@@ -106,6 +166,17 @@ error-extracting-file = BŁĄD: Nie udało się wyodrębnić plików: { $error }
 error-check-logs = BŁĄD: Sprawdź dziennik po więcej informacji.
 
 # Misc
+sort-name = Sort by: Name # TODO: Translate
+sort-dimensions = Sort by: Dimensions # TODO: Translate
+sort-duration = Sort by: Duration # TODO: Translate
+image-opaque = No alpha channel # TODO: Translate
+sort-size = Sort by: Size # TODO: Translate
+sort-modified = Sort by: Last modified # TODO: Translate
+sort-default = Sort by: Default # TODO: Translate
+image-format = Pixel format: { $format } # TODO: Translate
+image-alpha = Has an alpha channel # TODO: Translate
+image-dimensions = { $width } × { $height } pixels # TODO: Translate
+animation-frames = { $frames } frames, { $duration } seconds # TODO: Translate
 setting-below-restart-required = Note: Changing the setting below requires restarting the program for it to apply. # TODO: Translate
 support-project-donate = ♥ Wesprzyj projekt darowizną
 support-sponsor = ♥ Sponsoruj
@@ -3,6 +3,9 @@
 language-name = Shakespearian English
 
 # Tabs  
+snapshots = Snapshots # TODO: Translate
+meshes = Meshes # TODO: Translate
+videos = Videos # TODO: Translate
 music = Minstrelsy  
 sounds = Harmonious Tones  
 images = Painted Likenesses  
@@ -13,6 +16,17 @@ about = Of This Matter
 logs = Chronicles  
 
 # Buttons  
+input-worker-threads = Files read at once (0 uses every core) # TODO: Translate
+button-hide-duplicates = Stop showing duplicates # TODO: Translate
+button-show-duplicates = Show duplicates # TODO: Translate
+button-show-all = Show all # TODO: Translate
+button-find-similar = Find similar images # TODO: Translate
+input-similar-images-threshold = How different similar images can be # TODO: Translate
+button-pause = Pause # TODO: Translate
+button-play = Play # TODO: Translate
+button-extract-scripts = Extract scripts # TODO: Translate
+button-extract-type-as = Extract all of this type as { $format } # TODO: Translate
+button-extract-as = Extract as { $format } # TODO: Translate
 button-delete-this-dir = Cast This Folder into Oblivion <Del>  
 button-extract-type = Take Forth All of This Kind <F3>  
 button-refresh = Renew <F5>  
@@ -59,6 +73,7 @@ error-invalid-directory-description = Pray, ensure that the path thou hast given
 generic-error-critical = A Most Dire Misfortune!  
 
 # Headings  
+properties = Properties # TODO: Translate
 actions = Deeds  
 updates = Tidings of Change  
 language-settings = The Tongue’s Edicts  
@@ -68,6 +83,10 @@ dependencies = That Which is Required
 behavior = The Nature of Things  
 
 # Checkboxes  
+watch-cache = Update the list while Roblox is running # TODO: Translate
+detect-duplicates = Detect assets that are cached more than once (slower refreshing) # TODO: Translate
+extract-unique-only = Only extract one copy of assets that are cached more than once # TODO: Translate
+animate-previews = Play animated image previews # TODO: Translate
 check-for-updates = Seek Tidings of Change  
 automatically-install-updates = Set Forth Changes Without Bidding  
 use-alias = Bestow New Names Upon Thy Scrolls  
@@ -77,6 +96,36 @@ download-development-build = Partake of Unfinished Labors to Glimpse the Future
 checkbox-hide-user-logs = Conceal the Name of the User from the Chronicles  
 
 # Descriptions  
+button-save-snapshot = Save snapshot # TODO: Translate
+snapshot-saved = Saved the snapshot { $name } # TODO: Translate
+snapshots-identical = The snapshots have the same assets # TODO: Translate
+snapshot-added = Added # TODO: Translate
+snapshot-category-changes = { $category }: { $added } added, { $removed } removed, { $modified } modified # TODO: Translate
+compare-snapshots = Compare snapshots # TODO: Translate
+button-compare-snapshots = Compare # TODO: Translate
+snapshot-new = to # TODO: Translate
+reading-snapshot = Reading the cache for the snapshot ({ $item }/{ $total }) # TODO: Translate
+no-snapshots = No snapshots were saved yet # TODO: Translate
+snapshot-modified = Modified # TODO: Translate
+snapshot-old = From # TODO: Translate
+input-snapshot-name = Snapshot name # TODO: Translate
+snapshot-removed = Removed # TODO: Translate
+button-delete-snapshot = Delete { $name } # TODO: Translate
+snapshots-description = A snapshot remembers every asset in the cache, compare two of them to see which assets were added, removed or modified in between # TODO: Translate
+button-export-capture = Export captured assets # TODO: Translate
+no-captured-assets = No assets were added or changed during the capture # TODO: Translate
+button-stop-capture = Stop capture # TODO: Translate
+button-start-capture = Start capture # TODO: Translate
+capture-started = Capture started, play the game and stop the capture when you are done # TODO: Translate
+capture-stopped = Capture stopped # TODO: Translate
+captured-assets = Assets added or changed during the capture # TODO: Translate
+capturing-since = Capturing assets downloaded since { $time } # TODO: Translate
+badge-new = New # TODO: Translate
+duplicates = Assets that are cached more than once # TODO: Translate
+no-duplicates = No asset is cached more than once # TODO: Translate
+duplicate-copies = { $copies } copies # TODO: Translate
+similar-distance = { $distance } bits different # TODO: Translate
+similar-to = Images similar to "{ $asset }" # TODO: Translate
 clear-cache-description = If thy listing of scrolls taketh too long, and the retrieval is sluggish, thou mayest cleanse thy cache with the button below. The scrolls shall be reborn when next thou summoneth Roblox.  
 extract-all-description = The button below shall copy all relics and sort them into proper chambers, such as /harmonious_tones, /painted_likenesses. Thou mayest select the root chamber ere beginning.  
 custom-cache-dir-description = Shouldst thou wish to peer into another cache, alter its vault below. It may yet be restored to its former state anon. This is not the same as thy installation vault.  
@@ -87,7 +136,13 @@ logs-description = These chronicles dost show how the program fareth. Should mis
 copy-choose-file = Double-tap a scroll to copy  
 overwrite-with = Double-tap a scroll to replace with "{ $asset }"  
 
-# Statuses  
+# Statuses
+task-already-running = Another task is still running, try again when it has finished # TODO: Translate  
+checking-duplicates = Checking for duplicates ({ $item }/{ $total }) # TODO: Translate
+similar-images-found = Found { $count } similar images # TODO: Translate
+hashing-images = Comparing images ({ $item }/{ $total }) # TODO: Translate
+extracting-scripts = Extracting scripts ({ $item }/{ $total }) # TODO: Translate
+all-scripts-extracted = Extracted { $count } scripts # TODO: Translate
 idling = In a State of Idleness  
 deleting-files = Casting Scrolls into Oblivion ({ $item }/{ $total })  
 reading-files = Poring Over Scrolls ({ $item }/{ $total })  
@@ -98,7 +153,12 @@ stage = Stage { $stage }/{ $max }: { $status }
 swapped = Exchanged { $item_a } with { $item_b }  
 copied = Overwrote { $item_b } with { $item_a }  
 
-# Error Statuses  
+# Error Statuses
+error-converting-file = ERROR: Failed to convert: { $error } # TODO: Translate  
+error-audio-output = Sounds can't be heard as there is no audio output, check logs for more details. # TODO: Translate
+error-decoding-sound = ERROR: Failed to decode sound: { $error } # TODO: Translate
+error-reading-image = ERROR: Failed to read image: { $error } # TODO: Translate
+error-reading-model = ERROR: Failed to read model: { $error } # TODO: Translate
 failed-deleting-file = WOE! Could Not Cast Away ({ $item }/{ $total })  
 failed-opening-file = WOE! Could Not Unseal: { $error }  
 failed-not-file = WOE! '{ $file }' Is No True Scroll  
@@ -106,6 +166,17 @@ error-extracting-file = WOE! Could Not Retrieve: { $error }
 error-check-logs = WOE! Look to the Chronicles for Further Ills.  
 
 # Misc  
+sort-name = Sort by: Name # TODO: Translate
+sort-dimensions = Sort by: Dimensions # TODO: Translate
+sort-duration = Sort by: Duration # TODO: Translate
+image-opaque = No alpha channel # TODO: Translate
+sort-size = Sort by: Size # TODO: Translate
+sort-modified = Sort by: Last modified # TODO: Translate
+sort-default = Sort by: Default # TODO: Translate
+image-format = Pixel format: { $format } # TODO: Translate
+image-alpha = Has an alpha channel # TODO: Translate
+image-dimensions = { $width } × { $height } pixels # TODO: Translate
+animation-frames = { $frames } frames, { $duration } seconds # TODO: Translate
 no-function = (Yet It Doth Not Work)  
 version = Edition: v{ $version } (compiled upon { $date })  
 cache-directory = Cache Chamber: { $directory }  
@@ -3,6 +3,9 @@
 language-name = 简体中文
 
 # Tabs
+snapshots = Snapshots # TODO: Translate
+meshes = Meshes # TODO: Translate
+videos = Videos # TODO: Translate
 music = 音乐
 sounds = 音效
 images = 图片
@@ -13,6 +16,17 @@ about = 关于
 logs = 日志
 
 # Buttons
+input-worker-threads = Files read at once (0 uses every core) # TODO: Translate
+button-hide-duplicates = Stop showing duplicates # TODO: Translate
+button-show-duplicates = Show duplicates # TODO: Translate
+button-show-all = Show all # TODO: Translate
+button-find-similar = Find similar images # TODO: Translate
+input-similar-images-threshold = How different similar images can be # TODO: Translate
+button-pause = Pause # TODO: Translate
+button-play = Play # TODO: Translate
+button-extract-scripts = Extract scripts # TODO: Translate
+button-extract-type-as = Extract all of this type as { $format } # TODO: Translate
+button-extract-as = Extract as { $format } # TODO: Translate
 button-delete-this-dir = 删除此目录 <Del>
 button-extract-type = 提取此类型的所有文件 <F3>
 button-refresh = 刷新 <F5>
@@ -59,6 +73,7 @@ error-invalid-directory-description = 请确保您提供的路径是一个目录
 generic-error-critical = 严重错误
 
 # Headings
+properties = Properties # TODO: Translate
 actions = 操作
 updates = 更新
 language-settings = 语言设置
@@ -68,6 +83,10 @@ dependencies = 依赖项
 behavior = 可选项
 
 # Checkboxes
+watch-cache = Update the list while Roblox is running # TODO: Translate
+detect-duplicates = Detect assets that are cached more than once (slower refreshing) # TODO: Translate
+extract-unique-only = Only extract one copy of assets that are cached more than once # TODO: Translate
+animate-previews = Play animated image previews # TODO: Translate
 check-for-updates = 检查更新
 automatically-install-updates = 自动安装更新
 use-alias = 导出您重命名的文件名
@@ -77,6 +96,36 @@ download-development-build = 使用开发版本以提前获得功能
 checkbox-hide-user-logs = 从日志中隐藏用户名
 
 # Descriptions
+button-save-snapshot = Save snapshot # TODO: Translate
+snapshot-saved = Saved the snapshot { $name } # TODO: Translate
+snapshots-identical = The snapshots have the same assets # TODO: Translate
+snapshot-added = Added # TODO: Translate
+snapshot-category-changes = { $category }: { $added } added, { $removed } removed, { $modified } modified # TODO: Translate
+compare-snapshots = Compare snapshots # TODO: Translate
+button-compare-snapshots = Compare # TODO: Translate
+snapshot-new = to # TODO: Translate
+reading-snapshot = Reading the cache for the snapshot ({ $item }/{ $total }) # TODO: Translate
+no-snapshots = No snapshots were saved yet # TODO: Translate
+snapshot-modified = Modified # TODO: Translate
+snapshot-old = From # TODO: Translate
+input-snapshot-name = Snapshot name # TODO: Translate
+snapshot-removed = Removed # TODO: Translate
+button-delete-snapshot = Delete { $name } # TODO: Translate
+snapshots-description = A snapshot remembers every asset in the cache, compare two of them to see which assets were added, removed or modified in between # TODO: Translate
+button-export-capture = Export captured assets # TODO: Translate
+no-captured-assets = No assets were added or changed during the capture # TODO: Translate
+button-stop-capture = Stop capture # TODO: Translate
+button-start-capture = Start capture # TODO: Translate
+capture-started = Capture started, play the game and stop the capture when you are done # TODO: Translate
+capture-stopped = Capture stopped # TODO: Translate
+captured-assets = Assets added or changed during the capture # TODO: Translate
+capturing-since = Capturing assets downloaded since { $time } # TODO: Translate
+badge-new = New # TODO: Translate
+duplicates = Assets that are cached more than once # TODO: Translate
+no-duplicates = No asset is cached more than once # TODO: Translate
+duplicate-copies = { $copies } copies # TODO: Translate
+similar-distance = { $distance } bits different # TODO: Translate
+similar-to = Images similar to "{ $asset }" # TODO: Translate
 clear-cache-description = 如果从目录列出文件耗时太长，您可以清理Roblox缓存来帮助解决，客户端将在需要时重新生成这些文件
 extract-all-description = 此按钮将把所有资源提取到文件夹
 custom-cache-dir-description = 如果您想访问不同的缓存，可以在下方更改
@@ -88,6 +137,12 @@ copy-choose-file = 双击文件进行复制
 overwrite-with = 双击文件以用"{ $asset }"覆盖
 
 # Statuses
+task-already-running = Another task is still running, try again when it has finished # TODO: Translate
+checking-duplicates = Checking for duplicates ({ $item }/{ $total }) # TODO: Translate
+similar-images-found = Found { $count } similar images # TODO: Translate
+hashing-images = Comparing images ({ $item }/{ $total }) # TODO: Translate
+extracting-scripts = Extracting scripts ({ $item }/{ $total }) # TODO: Translate
+all-scripts-extracted = Extracted { $count } scripts # TODO: Translate
 idling = 空闲中
 deleting-files = 正在删除文件 ({ $item }/{ $total })
 reading-files = 正在读取文件 ({ $item }/{ $total })
@@ -99,6 +154,17 @@ swapped = 已将 { $item_a } 与 { $item_b } 替换
 copied = 已用 { $item_a } 覆盖 { $item_b }
 
 # Misc
+sort-name = Sort by: Name # TODO: Translate
+sort-dimensions = Sort by: Dimensions # TODO: Translate
+sort-duration = Sort by: Duration # TODO: Translate
+image-opaque = No alpha channel # TODO: Translate
+sort-size = Sort by: Size # TODO: Translate
+sort-modified = Sort by: Last modified # TODO: Translate
+sort-default = Sort by: Default # TODO: Translate
+image-format = Pixel format: { $format } # TODO: Translate
+image-alpha = Has an alpha channel # TODO: Translate
+image-dimensions = { $width } × { $height } pixels # TODO: Translate
+animation-frames = { $frames } frames, { $duration } seconds # TODO: Translate
 no-function = （尚未功能化）
 version = 版本: v{ $version } (编译于 { $date })
 cache-directory = 缓存目录: { $directory }
@@ -113,3 +179,9 @@ failed-opening-file = 错误：打开文件失败
 error-extracting-file = 错误：提取失败，原因：{ $error }
 error-check-logs = 错误，请检查日志
 failed-not-file = 错误 '{ $file }' 不是文件
+
+error-reading-model = ERROR: Failed to read model: { $error } # TODO: Translate
+error-reading-image = ERROR: Failed to read image: { $error } # TODO: Translate
+error-decoding-sound = ERROR: Failed to decode sound: { $error } # TODO: Translate
+error-audio-output = Sounds can't be heard as there is no audio output, check logs for more details. # TODO: Translate
+error-converting-file = ERROR: Failed to convert: { $error } # TODO: Translate
\ No newline at end of file
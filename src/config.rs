@@ -68,6 +68,15 @@ fn read_system_config() -> Value {
     }
 }
 
+/// Path of a file that is kept next to the config file, such as an index
+pub fn get_data_file(name: &str) -> PathBuf {
+    let config_file = CONFIG_FILE.lock().unwrap().clone();
+    match config_file.parent() {
+        Some(directory) => directory.join(name),
+        None => PathBuf::from(name),
+    }
+}
+
 pub fn get_config() -> Value {
     CONFIG.lock().unwrap().clone()
 }
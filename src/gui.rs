@@ -18,6 +18,8 @@ use lazy_static::lazy_static;
 
 mod welcome;
 mod settings;
+mod player;
+mod snapshots;
 
 const VERSION: &str = env!("CARGO_PKG_VERSION"); // Get version for use in the filename
 const COMPILE_DATE: &str = env!("COMPILE_DATE");
@@ -29,25 +31,39 @@ const CONTRIBUTORS: [&str; 5] = [
     "aaditkumar2009",
     "JustKanade",    
 ];
-const DEPENDENCIES: [[&str; 2]; 13] = [
-    ["https://github.com/emilk/egui", ""],
-    ["https://github.com/Adanos020/egui_dock", ""],
-    ["https://github.com/lampsitter/egui_commonmark", ""],
-    ["https://github.com/native-dialog-rs/native-dialog-rs", ""],
-    ["https://github.com/rust-lang-nursery/lazy-static.rs", ""],
-    ["https://github.com/projectfluent/fluent-rs", ""],
-    ["https://github.com/1Password/sys-locale", ""],
-    ["https://github.com/zbraniecki/unic-locale", ""],
-    ["https://github.com/Stebalien/tempfile", ""],
-    ["https://github.com/clap-rs/clap", ""],
-    ["https://github.com/ardaku/whoami", ""],
-    ["https://github.com/seanmonstar/reqwest", ""],
-    ["https://github.com/serde-rs/json", ""],
+// Repository, sponsor link and licence
+const DEPENDENCIES: [[&str; 3]; 22] = [
+    ["https://github.com/emilk/egui", "", "MIT OR Apache-2.0"],
+    ["https://github.com/Adanos020/egui_dock", "", "MIT"],
+    ["https://github.com/lampsitter/egui_commonmark", "", "MIT OR Apache-2.0"],
+    ["https://github.com/native-dialog-rs/native-dialog-rs", "", "MIT"],
+    ["https://github.com/rust-lang-nursery/lazy-static.rs", "", "MIT OR Apache-2.0"],
+    ["https://github.com/projectfluent/fluent-rs", "", "Apache-2.0 OR MIT"],
+    ["https://github.com/1Password/sys-locale", "", "MIT OR Apache-2.0"],
+    ["https://github.com/zbraniecki/unic-locale", "", "MIT OR Apache-2.0"],
+    ["https://github.com/Stebalien/tempfile", "", "MIT OR Apache-2.0"],
+    ["https://github.com/clap-rs/clap", "", "MIT OR Apache-2.0"],
+    ["https://github.com/ardaku/whoami", "", "Apache-2.0 OR BSL-1.0 OR MIT"],
+    ["https://github.com/seanmonstar/reqwest", "", "MIT OR Apache-2.0"],
+    ["https://github.com/serde-rs/json", "", "MIT OR Apache-2.0"],
+    ["https://github.com/rust-lang/flate2-rs", "", "MIT OR Apache-2.0"],
+    ["https://github.com/dropbox/rust-brotli-decompressor", "", "BSD-3-Clause OR MIT"],
+    ["https://github.com/KillingSpark/zstd-rs", "", "MIT"],
+    ["https://github.com/UniversalGameExtraction/texture2ddecoder", "", "MIT OR Apache-2.0"],
+    ["https://github.com/pseitz/lz4_flex", "", "MIT"],
+    ["https://github.com/marshallpierce/rust-base64", "", "MIT OR Apache-2.0"],
+    ["https://github.com/pdeljanov/Symphonia", "", "MPL-2.0"],
+    ["https://github.com/rustaudio/cpal", "", "Apache-2.0"],
+    ["https://github.com/notify-rs/notify", "", "CC0-1.0"],
 ];
 
 lazy_static! {
     static ref IMAGES: Mutex<HashMap<String, TextureHandle>> = Mutex::new(HashMap::new());
     static ref ASSETS_LOADING: Mutex<Vec<String>> = Mutex::new(Vec::new());
+    static ref MODEL: Mutex<Option<(String, ModelResult)>> = Mutex::new(None);
+    static ref ANIMATIONS: Mutex<HashMap<String, Arc<Animation>>> = Mutex::new(HashMap::new());
+    // Selected image, None while it is still being decoded
+    static ref DETAIL_IMAGE: Mutex<Option<(String, Option<DetailImageResult>)>> = Mutex::new(None);
 }
 
 struct TabViewer<'a> {
@@ -62,9 +78,17 @@ struct TabViewer<'a> {
     locale: &'a mut FluentBundle<Arc<FluentResource>>,
     asset_context_menu_open: &'a mut Option<usize>,
     copying: &'a mut bool,
+    player: &'a mut player::Player,
+    /// Image that similar images are being shown for
+    similar_to: &'a mut Option<String>,
+    /// Only assets that are cached more than once are being shown
+    showing_duplicates: &'a mut bool,
+    /// Only assets that were added or changed during the last capture are being shown
+    showing_capture: &'a mut bool,
+    snapshots: &'a mut snapshots::SnapshotsView,
 }
 
-fn double_click(dir: PathBuf, value: String, mode: String, swapping: &mut bool, copying: &mut bool, swapping_asset_a: &mut Option<String>) {
+fn double_click(dir: PathBuf, value: String, mode: String, swapping: &mut bool, copying: &mut bool, swapping_asset_a: &mut Option<String>, player: &mut player::Player) {
     if *copying {
         if swapping_asset_a.is_none() {
             *swapping_asset_a = Some(value);
@@ -79,17 +103,23 @@ fn double_click(dir: PathBuf, value: String, mode: String, swapping: &mut bool,
             *swapping_asset_a = None;
             *swapping = false
         }
+    } else if mode == "sounds" || mode == "music" {
+        // Played with the built-in player, other programs are only used for sounds it can't play
+        player.open(dir, &value, &mode, true);
     } else {
-        let temp_dir = logic::get_temp_dir(true);
-        let alias = config::get_asset_alias(&value);
-        let destination = temp_dir.join(alias);
-        let origin = dir.join(value);
-        let new_destination = logic::extract_file(origin, &mode, destination.clone(), true);
-        if new_destination != PathBuf::new() {
-            match open::that(new_destination) {
-                Ok(()) => (),
-                Err(err) => log::error(&format!("Failed opening file: {}", err))
-            }
+        open_in_other_program(dir, value, &mode);
+    }
+}
+
+fn open_in_other_program(dir: PathBuf, value: String, mode: &str) {
+    let temp_dir = logic::get_temp_dir(true);
+    let alias = config::get_asset_alias(&value);
+    let destination = temp_dir.join(alias);
+    let origin = dir.join(value);
+    if let Ok(new_destination) = logic::extract_file(origin, mode, destination.clone(), true, None) {
+        match open::that(new_destination) {
+            Ok(()) => (),
+            Err(err) => log::error(&format!("Failed opening file: {}", err))
         }
     }
 }
@@ -109,7 +139,7 @@ fn delete_this_directory(cache_directory: PathBuf, locale: &FluentBundle<Arc<Flu
     }
 }
 
-fn extract_all_of_type(cache_directory: PathBuf, mode: &str, locale: &FluentBundle<Arc<FluentResource>>) {
+fn extract_all_of_type(cache_directory: PathBuf, mode: &str, locale: &FluentBundle<Arc<FluentResource>>, convert_to: Option<&str>) {
     let mut no = logic::get_list_task_running();
 
     // Confirmation dialog, the program is still listing files
@@ -131,10 +161,24 @@ fn extract_all_of_type(cache_directory: PathBuf, mode: &str, locale: &FluentBund
 
         // If the user provides a directory, the program will extract the assets to that directory
         if let Some(path) = option_path {
-            logic::extract_dir(cache_directory, path, mode.to_string(), false,config::get_config_bool("use_alias").unwrap_or(false));
+            logic::extract_dir(cache_directory, path, mode.to_string(), false,config::get_config_bool("use_alias").unwrap_or(false), convert_to.map(str::to_owned), config::get_config_bool("extract_unique_only").unwrap_or(false));
         }
     }
 }
+fn export_capture() {
+    // Captured assets are sorted into a folder for each category, so only the root folder is chosen
+    if let Some(path) = DialogBuilder::file().open_single_dir().show().unwrap() {
+        logic::extract_captured(path, false, config::get_config_bool("use_alias").unwrap_or(false), config::get_config_bool("extract_unique_only").unwrap_or(false));
+    }
+}
+
+fn extract_scripts_of_type(cache_directory: PathBuf) {
+    // Scripts are written into folders for each model, so only the root folder is chosen
+    if let Some(path) = DialogBuilder::file().open_single_dir().show().unwrap() {
+        logic::extract_scripts(cache_directory, path, false);
+    }
+}
+
 fn toggle_swap(swapping: &mut bool, swapping_asset_a: &mut Option<String>, locale: &FluentBundle<Arc<FluentResource>>) {
     let mut warning_acknoledged = config::get_config_bool("ban-warning-ack").unwrap_or(false);
 
@@ -157,34 +201,92 @@ fn toggle_swap(swapping: &mut bool, swapping_asset_a: &mut Option<String>, local
     }
 }
 
-fn extract_file_button(name: &str, cache_directory: PathBuf, tab: &str) {
-    let alias = config::get_asset_alias(name);
+fn extract_file_button(name: &str, cache_directory: PathBuf, tab: &str, convert_to: Option<&str>) {
+    let mut alias = config::get_asset_alias(name);
+    if let Some(format) = convert_to {
+        alias = format!("{}.{}", alias, format);
+    }
     let origin = cache_directory.join(name);
     if let Some(destination) = native_dialog::DialogBuilder::file().set_filename(&alias).save_single_file().show().unwrap() {
-        logic::extract_file(origin, tab.into(), destination, false);
+        // The status bar tells the user if it couldn't be converted
+        let _ = logic::extract_file(origin, tab, destination, false, convert_to);
     }
 }
 
+// Tabs where assets can be shown as images in a grid
+fn has_image_preview(tab: &str) -> bool {
+    tab == "images" || tab == "ktx-files" || tab == "meshes" || tab == "sounds" || tab == "music"
+}
+
+fn create_texture(id: &str, image: &image::RgbaImage, ctx: &egui::Context) -> TextureHandle {
+    let size = [image.width() as usize, image.height() as usize];
+    ctx.load_texture(
+        id,
+        egui::ColorImage::from_rgba_unmultiplied(size, image.as_flat_samples().as_slice()),
+        Default::default(),
+    )
+}
+
+fn load_texture(id: &str, image: &image::RgbaImage, ctx: egui::Context) -> TextureHandle {
+    let texture = create_texture(id, image, &ctx);
+    let mut images = IMAGES.lock().unwrap();
+    images.insert(id.to_string(), texture.clone());
+    return texture;
+}
+
 fn load_image(id: &str, data: &[u8], ctx: egui::Context) -> Result<TextureHandle, image::ImageError> {
     let images = {IMAGES.lock().unwrap().clone()};
     if let Some(texture) = images.get(id) {
         Ok(texture.clone())
     } else {
         let icon_image = image::load_from_memory(data)?;
-        let icon_rgba = icon_image.to_rgba8();
-        let icon_size = [icon_rgba.width() as usize, icon_rgba.height() as usize];
-        let texture = ctx.load_texture(
-            id,
-            egui::ColorImage::from_rgba_unmultiplied(icon_size, icon_rgba.as_flat_samples().as_slice()),
-            Default::default(),
-        );
-        let mut images = IMAGES.lock().unwrap();
-        images.insert(id.to_string(), texture.clone());
-        return Ok(texture);
+        return Ok(load_texture(id, &icon_image.to_rgba8(), ctx));
+    }
+}
+
+/// Frames of an animated image, each shown for its own delay
+struct Animation {
+    frames: Vec<(TextureHandle, Duration)>,
+    length: Duration,
+}
+
+impl Animation {
+    fn new(id: &str, frames: Vec<logic::animation::Frame>, ctx: &egui::Context) -> Animation {
+        let frames: Vec<(TextureHandle, Duration)> = frames.iter().enumerate()
+            .map(|(i, frame)| (create_texture(&format!("{}#{}", id, i), &frame.image, ctx), frame.delay))
+            .collect();
+        let length = frames.iter().map(|(_, delay)| *delay).sum();
+        Animation { frames, length }
+    }
+
+    /// Frame to show at the current time, a repaint is requested for when it changes
+    fn current_frame(&self, ctx: &egui::Context) -> TextureHandle {
+        let time = ctx.input(|i| i.time) % self.length.as_secs_f64();
+        let position = Duration::from_secs_f64(time);
+        let mut end = Duration::ZERO;
+        for (texture, delay) in &self.frames {
+            end += *delay;
+            if position < end {
+                ctx.request_repaint_after(end - position);
+                return texture.clone()
+            }
+        }
+        self.frames[0].0.clone()
     }
 }
 
+/// Forget every preview so they are loaded again, such as after the animation setting changes
+fn clear_image_previews() {
+    IMAGES.lock().unwrap().clear();
+    ANIMATIONS.lock().unwrap().clear();
+    *DETAIL_IMAGE.lock().unwrap() = None;
+}
+
 fn load_asset_image(id: String, tab: String, cache_directory: PathBuf, ctx: egui::Context) -> Option<TextureHandle> {
+    let animation = {ANIMATIONS.lock().unwrap().get(&id).cloned()};
+    if let Some(animation) = animation {
+        return Some(animation.current_frame(&ctx))
+    }
     let images = {IMAGES.lock().unwrap().clone()};
     if let Some(texture) = images.get(&id) {
         Some(texture.clone())
@@ -202,8 +304,30 @@ fn load_asset_image(id: String, tab: String, cache_directory: PathBuf, ctx: egui
             }
             let path = cache_directory.join(&id);
             let bytes = logic::extract_file_to_bytes(path, &tab);
-            match load_image(&id, &bytes.as_slice(), ctx) {
-                Ok(_) => {
+            let preview_size = config::get_config_u64("image_preview_size").unwrap_or(128) as u32;
+            if tab == "images" && config::get_config_bool("animate_previews").unwrap_or(true) {
+                match logic::decode_animation(&bytes, Some(preview_size)) {
+                    Ok(frames) if !frames.is_empty() => {
+                        let animation = Animation::new(&id, frames, &ctx);
+                        ANIMATIONS.lock().unwrap().insert(id.clone(), Arc::new(animation));
+                        let mut assets_loading = ASSETS_LOADING.lock().unwrap();
+                        assets_loading.retain(|x| x != &id); // Remove the asset from the loading set
+                        return
+                    }
+                    Ok(_) => (), // Not animated, load it as a still image
+                    Err(e) => log::warn(&format!("Failed to decode the frames of {}: {}", &id, e)),
+                }
+            }
+            let image = if tab == "meshes" {
+                logic::render_mesh(&bytes, preview_size)
+            } else if tab == "sounds" || tab == "music" {
+                logic::render_waveform(&bytes, preview_size)
+            } else {
+                logic::decode_image(&bytes, Some(preview_size))
+            };
+            match image {
+                Ok(image) => {
+                    let _ = load_texture(&id, &image, ctx);
                     let mut assets_loading = ASSETS_LOADING.lock().unwrap();
                     assets_loading.retain(|x| x != &id); // Remove the asset from the loading set
                 },
@@ -220,16 +344,98 @@ fn load_asset_image(id: String, tab: String, cache_directory: PathBuf, ctx: egui
     }
 }
 
-fn add_dependency_credit(dependency: [&str;2], ui: &mut egui::Ui, sponsor_message: &str) {
-    if dependency[1] != "" {
-        ui.horizontal(|ui| {
-            ui.hyperlink_to(dependency[0].replace("https://github.com/", ""), dependency[0]);
-            ui.label("|");
-            ui.hyperlink_to(sponsor_message, dependency[1]);
-        });
+// Tabs where the selected asset has a details pane
+fn has_details_pane(tab: &str) -> bool {
+    tab == "rbxm-files" || tab == "images" || tab == "ktx-files" || tab == "sounds" || tab == "music"
+}
+
+/// Selected image at its full size
+struct DetailImage {
+    width: u32,
+    height: u32,
+    texture: TextureHandle,
+    animation: Option<Animation>,
+}
+
+type DetailImageResult = Result<Arc<DetailImage>, String>;
+
+/// None while the image is loading, decoding every frame of an animation takes a while so it is done on another thread
+fn load_detail_image(name: &str, tab: &str, cache_directory: PathBuf, ctx: &egui::Context) -> Option<DetailImageResult> {
+    // Only the selected image is kept, like the selected model
+    let mut detail_image = DETAIL_IMAGE.lock().unwrap();
+    if let Some((cached_name, result)) = &*detail_image {
+        if cached_name == name {
+            return result.clone()
+        }
+    }
+    *detail_image = Some((name.to_owned(), None));
+
+    let (name, tab, ctx) = (name.to_owned(), tab.to_owned(), ctx.clone());
+    thread::spawn(move || {
+        let result = decode_detail_image(&name, &tab, cache_directory, &ctx);
+        let mut detail_image = DETAIL_IMAGE.lock().unwrap();
+        // Another image may have been selected while this one was loading
+        if detail_image.as_ref().is_some_and(|(selected, _)| *selected == name) {
+            *detail_image = Some((name, Some(result)));
+            ctx.request_repaint();
+        }
+    });
+    None
+}
+
+fn decode_detail_image(name: &str, tab: &str, cache_directory: PathBuf, ctx: &egui::Context) -> DetailImageResult {
+    let bytes = logic::extract_file_to_bytes(cache_directory.join(name), tab);
+    let frames = if tab == "images" && config::get_config_bool("animate_previews").unwrap_or(true) {
+        logic::decode_animation(&bytes, None).unwrap_or_default()
     } else {
-        ui.hyperlink_to(dependency[0].replace("https://github.com/", ""), dependency[0]);
+        Vec::new()
+    };
+    let result = if let Some(first) = frames.first() {
+        let (width, height) = first.image.dimensions();
+        let animation = Animation::new(name, frames, ctx);
+        Ok(DetailImage { width, height, texture: animation.frames[0].0.clone(), animation: Some(animation) })
+    } else {
+        logic::decode_image(&bytes, None).map(|image| DetailImage {
+            width: image.width(),
+            height: image.height(),
+            texture: create_texture(name, &image, ctx),
+            animation: None,
+        }).map_err(|e| e.to_string())
+    };
+    let result = result.map(Arc::new);
+    if let Err(e) = &result {
+        log::warn(&format!("Failed to read image {}: {}", name, e));
+    }
+    result
+}
+
+type ModelResult = Result<Arc<logic::rbxm::Model>, String>;
+
+fn load_model(name: &str, cache_directory: PathBuf) -> ModelResult {
+    // Only the selected model is kept, so it isn't parsed again every frame
+    let mut model = MODEL.lock().unwrap();
+    if let Some((cached_name, result)) = &*model {
+        if cached_name == name {
+            return result.clone()
+        }
+    }
+    let result = logic::read_model(cache_directory.join(name)).map(Arc::new);
+    if let Err(e) = &result {
+        log::warn(&format!("Failed to read model {}: {}", name, e));
     }
+    *model = Some((name.to_owned(), result.clone()));
+    return result;
+}
+
+fn add_dependency_credit(dependency: [&str;3], ui: &mut egui::Ui, sponsor_message: &str) {
+    ui.horizontal(|ui| {
+        ui.hyperlink_to(dependency[0].replace("https://github.com/", ""), dependency[0]);
+        ui.weak(dependency[2]);
+        if dependency[1] != "" {
+            ui.label("|");
+            ui.hyperlink_to(sponsor_message, dependency[1]);
+        }
+    });
 }
 
 fn format_size(bytes: u64) -> String {
@@ -244,6 +450,38 @@ fn format_size(bytes: u64) -> String {
     format!("{:.1} {}", size, UNITS[unit_idx])
 }
 
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+fn format_audio(audio: &logic::AudioInfo) -> String {
+    let mut parts = Vec::new();
+    if let Some(duration) = audio.duration {
+        parts.push(format_duration(duration));
+    }
+    parts.push(audio.codec.clone());
+    parts.push(format!("{} kHz", audio.sample_rate as f32 / 1000.0));
+    parts.push(format!("{} ch", audio.channels));
+    if let Some(bitrate) = audio.bitrate {
+        parts.push(format!("{} kbps", bitrate / 1000));
+    }
+    parts.join(" · ")
+}
+
+fn format_image(image: &logic::ImageInfo) -> String {
+    format!("{}×{} · {}{}", image.width, image.height, image.format, if image.alpha { " · alpha" } else { "" })
+}
+
+/// Small label next to assets that were added to the cache while the tab was open
+fn new_badge(ui: &egui::Ui, position: egui::Pos2, locale: &FluentBundle<Arc<FluentResource>>) {
+    let visuals = ui.visuals();
+    let galley = ui.painter().layout_no_wrap(locale::get_message(locale, "badge-new", None), egui::TextStyle::Small.resolve(ui.style()), visuals.strong_text_color());
+    let rect = egui::Rect::from_min_size(position, galley.size() + egui::vec2(6.0, 2.0));
+    ui.painter().rect_filled(rect, 3.0, visuals.selection.bg_fill);
+    ui.painter().galley(rect.min + egui::vec2(3.0, 1.0), galley, visuals.strong_text_color());
+}
+
 fn format_modified(time: std::time::SystemTime) -> String {
     let datetime: chrono::DateTime<chrono::Local> = time.into();
     datetime.format("%Y-%m-%d %H:%M").to_string()
@@ -253,11 +491,23 @@ impl TabViewer<'_> {
     fn asset_buttons(&mut self, ui: &mut egui::Ui, cache_directory: PathBuf, tab: &str, focus_search_box: &mut bool, name: Option<&str>) {
         if let Some(name) = name {
             if ui.button(locale::get_message(self.locale, "button-open", None)).clicked() {
-                double_click(cache_directory.clone(), name.to_string(), tab.to_string(), self.swapping, self.copying, self.swapping_asset_a);
+                double_click(cache_directory.clone(), name.to_string(), tab.to_string(), self.swapping, self.copying, self.swapping_asset_a, self.player);
                 *self.asset_context_menu_open = None;
             }
             if ui.button(locale::get_message(self.locale, "button-extract-file", None)).clicked() {
-                extract_file_button(name, cache_directory.clone(), tab);
+                extract_file_button(name, cache_directory.clone(), tab, None);
+                *self.asset_context_menu_open = None;
+            }
+            for format in logic::get_conversions(tab) {
+                let mut args = fluent_bundle::FluentArgs::new();
+                args.set("format", format.to_uppercase());
+                if ui.button(locale::get_message(self.locale, "button-extract-as", Some(&args))).clicked() {
+                    extract_file_button(name, cache_directory.clone(), tab, Some(format));
+                    *self.asset_context_menu_open = None;
+                }
+            }
+            if tab == "images" && ui.button(locale::get_message(self.locale, "button-find-similar", None)).clicked() {
+                self.find_similar(cache_directory.clone(), name);
                 *self.asset_context_menu_open = None;
             }
         }
@@ -278,13 +528,35 @@ impl TabViewer<'_> {
             *self.asset_context_menu_open = None;
         }
         if ui.button(locale::get_message(self.locale, "button-extract-type", None)).clicked() {
-            extract_all_of_type(cache_directory.clone(), tab, self.locale);
+            extract_all_of_type(cache_directory.clone(), tab, self.locale, None);
+            *self.asset_context_menu_open = None;
+        }
+        for format in logic::get_conversions(tab) {
+            let mut args = fluent_bundle::FluentArgs::new();
+            args.set("format", format.to_uppercase());
+            if ui.button(locale::get_message(self.locale, "button-extract-type-as", Some(&args))).clicked() {
+                extract_all_of_type(cache_directory.clone(), tab, self.locale, Some(format));
+                *self.asset_context_menu_open = None;
+            }
+        }
+        if tab == "rbxm-files" && ui.button(locale::get_message(self.locale, "button-extract-scripts", None)).clicked() {
+            extract_scripts_of_type(cache_directory.clone());
             *self.asset_context_menu_open = None;
         }
         if ui.button(locale::get_message(self.locale, "button-refresh", None)).clicked() {
             logic::refresh(cache_directory.clone(), tab.to_string(), false, false);
             *self.asset_context_menu_open = None;
         }
+        let message = if *self.showing_duplicates { "button-hide-duplicates" } else { "button-show-duplicates" };
+        if ui.button(locale::get_message(self.locale, message, None)).clicked() {
+            self.toggle_duplicates(cache_directory.clone(), tab);
+            *self.asset_context_menu_open = None;
+        }
+        let message = if logic::get_capture_start().is_some() { "button-stop-capture" } else { "button-start-capture" };
+        if ui.button(locale::get_message(self.locale, message, None)).clicked() {
+            self.toggle_capture(cache_directory.clone(), tab);
+            *self.asset_context_menu_open = None;
+        }
         if ui.button(locale::get_message(self.locale, "button-swap", None)).clicked() {
             toggle_swap(self.swapping, self.swapping_asset_a, self.locale);
             *self.asset_context_menu_open = None;
@@ -307,7 +579,7 @@ impl TabViewer<'_> {
             }
         }
 
-        if tab == "images" {
+        if has_image_preview(tab) {
             let message = if config::get_config_bool("display_image_preview").unwrap_or(false) {
                 locale::get_message(self.locale, "button-disable-display-image-preview", None)
             } else {
@@ -321,6 +593,152 @@ impl TabViewer<'_> {
         }
     }
     
+    fn toggle_duplicates(&mut self, cache_directory: PathBuf, tab: &str) {
+        *self.showing_duplicates = !*self.showing_duplicates;
+        *self.similar_to = None;
+        *self.showing_capture = false;
+        *self.selected = None; // The list changes, so the selection would point to another asset
+        if *self.showing_duplicates && !logic::get_detect_duplicates() {
+            // The assets have to be read again to hash them
+            logic::detect_duplicates_this_session();
+            logic::refresh(cache_directory, tab.to_owned(), false, false);
+        }
+    }
+
+    fn toggle_capture(&mut self, cache_directory: PathBuf, tab: &str) {
+        if logic::stop_capture() {
+            *self.showing_capture = true;
+            *self.similar_to = None;
+            *self.showing_duplicates = false;
+            *self.selected = None; // The list changes, so the selection would point to another asset
+            logic::refresh(cache_directory, tab.to_owned(), false, false); // List what the game downloaded
+        } else {
+            logic::start_capture();
+            *self.showing_capture = false;
+        }
+    }
+
+    fn find_similar(&mut self, cache_directory: PathBuf, name: &str) {
+        // The view only changes if the search started, the status bar says why if it didn't
+        if logic::find_similar_images(cache_directory, name.to_owned(), false) {
+            *self.similar_to = Some(name.to_owned());
+            *self.showing_duplicates = false;
+            *self.showing_capture = false;
+            *self.selected = None; // The list changes, so the selection would point to another asset
+        }
+    }
+
+    fn sort_combo_box(&mut self, ui: &mut egui::Ui) {
+        let sort_by = config::get_config_string("sort_by").unwrap_or("default".to_owned());
+        let mut selected = sort_by.clone();
+        egui::ComboBox::from_id_salt("sort_by")
+        .selected_text(locale::get_message(self.locale, &format!("sort-{}", sort_by), None))
+        .show_ui(ui, |ui| {
+            for key in ["default", "name", "size", "modified", "dimensions", "duration"] {
+                ui.selectable_value(&mut selected, key.to_owned(), locale::get_message(self.locale, &format!("sort-{}", key), None));
+            }
+        });
+        if selected != sort_by {
+            config::set_config_value("sort_by", selected.clone().into());
+            logic::sort_file_list(&selected);
+        }
+    }
+
+    fn details_pane(&mut self, ui: &mut egui::Ui, cache_directory: PathBuf, tab: &str, asset: &logic::AssetInfo) {
+        let name = asset.name.as_str();
+        ui.heading(config::get_asset_alias(name));
+        ui.separator();
+
+        if tab == "rbxm-files" {
+            match load_model(name, cache_directory) {
+                Ok(model) => {
+                    for root in &model.roots {
+                        self.instance_tree(ui, &model, *root);
+                    }
+                }
+                Err(e) => {
+                    let mut args = fluent_bundle::FluentArgs::new();
+                    args.set("error", e);
+                    ui.label(locale::get_message(self.locale, "error-reading-model", Some(&args)));
+                }
+            }
+        } else if tab == "images" || tab == "ktx-files" {
+            // Format and alpha are read from the headers while refreshing, the image can't tell
+            if let Some(info) = &asset.image {
+                let mut args = fluent_bundle::FluentArgs::new();
+                args.set("format", info.format.clone());
+                ui.label(locale::get_message(self.locale, "image-format", Some(&args)));
+                ui.label(locale::get_message(self.locale, if info.alpha { "image-alpha" } else { "image-opaque" }, None));
+            }
+            if tab == "images" && ui.button(locale::get_message(self.locale, "button-find-similar", None)).clicked() {
+                self.find_similar(cache_directory.clone(), name);
+            }
+            match load_detail_image(name, tab, cache_directory, ui.ctx()) {
+                None => {
+                    ui.spinner();
+                }
+                Some(Ok(image)) => {
+                    let mut args = fluent_bundle::FluentArgs::new();
+                    args.set("width", image.width);
+                    args.set("height", image.height);
+                    ui.label(locale::get_message(self.locale, "image-dimensions", Some(&args)));
+
+                    let texture = match &image.animation {
+                        Some(animation) => {
+                            let mut args = fluent_bundle::FluentArgs::new();
+                            args.set("frames", animation.frames.len());
+                            args.set("duration", format!("{:.2}", animation.length.as_secs_f64()));
+                            ui.label(locale::get_message(self.locale, "animation-frames", Some(&args)));
+                            animation.current_frame(ui.ctx())
+                        }
+                        None => image.texture.clone(),
+                    };
+                    ui.add(egui::Image::new(&texture).maintain_aspect_ratio(true).max_width(ui.available_width()));
+                }
+                Some(Err(e)) => {
+                    let mut args = fluent_bundle::FluentArgs::new();
+                    args.set("error", e);
+                    ui.label(locale::get_message(self.locale, "error-reading-image", Some(&args)));
+                }
+            }
+        } else if tab == "sounds" || tab == "music" {
+            self.player.ui(ui, self.locale, cache_directory, name, tab);
+        }
+    }
+
+    fn instance_tree(&mut self, ui: &mut egui::Ui, model: &logic::rbxm::Model, index: usize) {
+        let instance = &model.instances[index];
+        egui::CollapsingHeader::new(instance.label())
+        .id_salt(("instance", index))
+        .default_open(instance.parent.is_none())
+        .show(ui, |ui| {
+            for (name, value) in instance.key_properties() {
+                ui.label(format!("{}: {}", name, value));
+            }
+
+            egui::CollapsingHeader::new(locale::get_message(self.locale, "properties", None))
+            .id_salt(("properties", index))
+            .show(ui, |ui| {
+                for (name, value) in &instance.properties {
+                    // Show the instance a reference points to instead of its id
+                    let value = match value {
+                        logic::rbxm::Value::Ref(Some(referent))
+                        | logic::rbxm::Value::Content(logic::rbxm::Content::Object(referent)) => match model.find_referent(*referent) {
+                            Some(target) => target.label(),
+                            None => value.to_string(),
+                        },
+                        _ => value.to_string(),
+                    };
+                    ui.label(format!("{}: {}", name, value));
+                }
+            });
+
+            for child in &instance.children {
+                self.instance_tree(ui, model, *child);
+            }
+        });
+    }
+
     // Function to handle asset response within asset list
     fn handle_asset_response(
         &mut self,
@@ -369,7 +787,7 @@ impl TabViewer<'_> {
         }
 
         if response.double_clicked() {
-            double_click(cache_directory, file_name.to_string(), tab.to_string(), self.swapping, self.copying, self.swapping_asset_a);
+            double_click(cache_directory, file_name.to_string(), tab.to_string(), self.swapping, self.copying, self.swapping_asset_a, self.player);
         }
 
         // Handle keyboard scrolling
@@ -416,17 +834,21 @@ impl egui_dock::TabViewer for TabViewer<'_> {
 
         let file_list = logic::get_file_list(); // Get the file list as it is used throughout the GUI
 
-        if tab != "settings" && tab != "about" && tab != "logs" {
+        if tab != "settings" && tab != "about" && tab != "logs" && tab != "snapshots" {
             // This is only shown on tabs other than settings (Extracting assets)
 
             // Detect if tab changed and do a refresh if so
             if let Some(current_tab) = self.current_tab {
                 if current_tab.to_owned() != tab.to_owned() {
                     *self.current_tab = Some(tab.to_owned());
+                    *self.similar_to = None;
+                    *self.showing_duplicates = false;
+                    logic::clear_new_assets();
                     logic::refresh(cache_directory.to_owned(), tab.to_owned(), false, false);
                 }
             } else {
                 *self.current_tab = Some(tab.to_owned());
+                logic::clear_new_assets();
                 logic::refresh(cache_directory.to_owned(), tab.to_owned(), false, false);
             }
 
@@ -447,7 +869,7 @@ impl egui_dock::TabViewer for TabViewer<'_> {
                 delete_this_directory(cache_directory.clone(), self.locale);
             }
             if ui.input(|i| i.key_pressed(egui::Key::F3)) {
-                extract_all_of_type(cache_directory.clone(), &tab, self.locale);
+                extract_all_of_type(cache_directory.clone(), &tab, self.locale, None);
             }
             if ui.input(|i| i.key_pressed(egui::Key::F5)) {
                 logic::refresh(cache_directory.clone(), tab.to_owned(), false, false);
@@ -468,9 +890,14 @@ impl egui_dock::TabViewer for TabViewer<'_> {
                     egui::ScrollArea::horizontal().show(ui, |ui| {
                         ui.horizontal(|ui| {
                             self.asset_buttons(ui, cache_directory.clone(), tab, &mut focus_search_box, None);
+                            ui.separator();
+                            self.sort_combo_box(ui);
                         });
                     })
                 });
+            } else {
+                // Sorting isn't one of the asset buttons, so it still needs a place without the topbar
+                ui.horizontal(|ui| self.sort_combo_box(ui));
             }
             
             let mut scroll_to: Option<usize> = None; // This is reset every frame, so it doesn't constantly scroll to the same label
@@ -507,7 +934,7 @@ impl egui_dock::TabViewer for TabViewer<'_> {
                     if let Some(selected) = *self.selected {
                         // Get file name after getting the selected value
                         if let Some(asset) = file_list.get(selected) {
-                            double_click(cache_directory.clone(), asset.name.to_string(), tab.to_string(), self.swapping, self.copying, self.swapping_asset_a);
+                            double_click(cache_directory.clone(), asset.name.to_string(), tab.to_string(), self.swapping, self.copying, self.swapping_asset_a, self.player);
                         }                   
                     }
                 }
@@ -517,7 +944,7 @@ impl egui_dock::TabViewer for TabViewer<'_> {
                     if let Some(selected) = *self.selected {
                         // Get file name after getting the selected value
                         if let Some(asset) = file_list.get(selected) {
-                            extract_file_button(&asset.name, cache_directory.clone(), tab);
+                            extract_file_button(&asset.name, cache_directory.clone(), tab, None);
                         }                   
                     }
                 }
@@ -546,7 +973,64 @@ impl egui_dock::TabViewer for TabViewer<'_> {
                 }
             }
 
-            let file_list = if *self.searching {
+            if let Some(since) = logic::get_capture_start() {
+                let mut args = fluent_bundle::FluentArgs::new();
+                args.set("time", format_modified(since));
+                ui.heading(locale::get_message(self.locale, "capturing-since", Some(&args)));
+            }
+
+            // Similar images are shown with how far they are from the one that was searched for,
+            // duplicates with how many copies there are
+            let mut row_details = HashMap::new();
+            let file_list = if let Some(similar_to) = self.similar_to.clone() {
+                let mut show_all = false;
+                ui.horizontal(|ui| {
+                    let mut args = fluent_bundle::FluentArgs::new();
+                    args.set("asset", config::get_asset_alias(&similar_to));
+                    ui.heading(locale::get_message(self.locale, "similar-to", Some(&args)));
+                    show_all = ui.button(locale::get_message(self.locale, "button-show-all", None)).clicked();
+                });
+                if show_all {
+                    *self.similar_to = None;
+                    *self.selected = None;
+                }
+                logic::get_similar_file_list().into_iter().map(|(asset, distance)| {
+                    let mut args = fluent_bundle::FluentArgs::new();
+                    args.set("distance", distance);
+                    row_details.insert(asset.name.clone(), locale::get_message(self.locale, "similar-distance", Some(&args)));
+                    asset
+                }).collect()
+            } else if *self.showing_duplicates {
+                let duplicates = logic::get_duplicate_file_list();
+                ui.horizontal(|ui| {
+                    let message = if duplicates.is_empty() { "no-duplicates" } else { "duplicates" };
+                    ui.heading(locale::get_message(self.locale, message, None));
+                    if ui.button(locale::get_message(self.locale, "button-show-all", None)).clicked() {
+                        *self.showing_duplicates = false;
+                        *self.selected = None;
+                    }
+                });
+                duplicates.into_iter().map(|(asset, copies)| {
+                    let mut args = fluent_bundle::FluentArgs::new();
+                    args.set("copies", copies);
+                    row_details.insert(asset.name.clone(), locale::get_message(self.locale, "duplicate-copies", Some(&args)));
+                    asset
+                }).collect()
+            } else if *self.showing_capture {
+                let captured = logic::get_captured_file_list(tab).unwrap_or_default();
+                ui.horizontal(|ui| {
+                    let message = if captured.is_empty() { "no-captured-assets" } else { "captured-assets" };
+                    ui.heading(locale::get_message(self.locale, message, None));
+                    if ui.button(locale::get_message(self.locale, "button-export-capture", None)).clicked() {
+                        export_capture();
+                    }
+                    if ui.button(locale::get_message(self.locale, "button-show-all", None)).clicked() {
+                        *self.showing_capture = false;
+                        *self.selected = None;
+                    }
+                });
+                captured
+            } else if *self.searching {
                 let old_search_query = self.search_query.clone();
 
                 let response = ui.text_edit_singleline(self.search_query);
@@ -567,7 +1051,19 @@ impl egui_dock::TabViewer for TabViewer<'_> {
                 file_list
             };
 
-            let display_image_preview = config::get_config_bool("display_image_preview").unwrap_or(false) && tab == "images";
+            // Details of the selected asset are shown next to the list
+            if has_details_pane(tab) {
+                if let Some(asset) = (*self.selected).and_then(|i| file_list.get(i)).filter(|asset| asset.real_asset) {
+                    egui::SidePanel::right("details_pane").resizable(true).default_width(ui.available_width() / 3.0).show_inside(ui, |ui| {
+                        egui::ScrollArea::both().show(ui, |ui| {
+                            self.details_pane(ui, cache_directory.clone(), tab, asset);
+                        });
+                    });
+                }
+            }
+
+            let display_image_preview = config::get_config_bool("display_image_preview").unwrap_or(false) && has_image_preview(tab);
+            let new_assets = logic::get_new_assets(); // Assets that appeared while the tab was open get a badge
 
             let row_height = if display_image_preview {
                 config::get_config_u64("image_preview_size").unwrap_or(128) as f32
@@ -715,6 +1211,18 @@ impl egui_dock::TabViewer for TabViewer<'_> {
                                             ui.painter().rect_filled(text_rect, 0.0, background_colour);
         
                                             ui.put(text_rect, text);
+
+                                            // Length of sounds in the bottom right corner
+                                            if let Some(duration) = asset.audio.as_ref().and_then(|audio| audio.duration) {
+                                                let galley = ui.painter().layout_no_wrap(format_duration(duration), egui::TextStyle::Body.resolve(ui.style()), text_colour);
+                                                let background_rect = egui::Rect::from_min_max(rect.max - galley.size(), rect.max);
+                                                ui.painter().rect_filled(background_rect, 0.0, background_colour);
+                                                ui.painter().galley(background_rect.min, galley, text_colour);
+                                            }
+
+                                            if new_assets.contains(file_name) {
+                                                new_badge(ui, rect.min, self.locale);
+                                            }
                                         }
                                     }
                                 }    
@@ -765,13 +1273,32 @@ impl egui_dock::TabViewer for TabViewer<'_> {
                                     // let modified_x = rect.min.x + rect.width() * 1.0 - 5.0; // adjust for padding
             
                                     // Draw all columns
-                                    ui.painter().text(
+                                    let alias_rect = ui.painter().text(
                                         egui::pos2(alias_x, rect.min.y),
                                         egui::Align2::LEFT_TOP,
                                         alias,
                                         egui::TextStyle::Body.resolve(ui.style()),
                                         text_colour,
                                     );
+
+                                    if new_assets.contains(&asset.name) {
+                                        new_badge(ui, egui::pos2(alias_rect.max.x + 5.0, rect.min.y), self.locale);
+                                    }
+
+                                    // Distance of similar images, copies of duplicates, length and format of sounds, or size and format of images on the right
+                                    let details = match row_details.get(&asset.name) {
+                                        Some(details) => Some(details.clone()),
+                                        None => asset.audio.as_ref().map(format_audio).or(asset.image.as_ref().map(format_image)),
+                                    };
+                                    if let Some(details) = details {
+                                        ui.painter().text(
+                                            egui::pos2(rect.max.x - 5.0, rect.min.y),
+                                            egui::Align2::RIGHT_TOP,
+                                            details,
+                                            egui::TextStyle::Body.resolve(ui.style()),
+                                            visuals.weak_text_color(),
+                                        );
+                                    }
                                 
                                 // These are for later, broken rn
                                 //     ui.painter().text(
@@ -814,6 +1341,10 @@ impl egui_dock::TabViewer for TabViewer<'_> {
                 *self.locale = locale::get_locale(None);
             }
 
+        } else if tab == "snapshots" {
+            snapshots::save(ui, self.locale, self.snapshots);
+            snapshots::compare(ui, self.locale, self.snapshots);
+
         } else if tab == "logs" {
             ui.heading(locale::get_message(self.locale, "logs", None));
             ui.label(locale::get_message(self.locale, "logs-description", None));
@@ -927,11 +1458,20 @@ struct MyApp {
     locale: FluentBundle<Arc<FluentResource>>,
     asset_context_menu_open: Option<usize>,
     copying: bool,
+    player: player::Player,
+    similar_to: Option<String>,
+    showing_duplicates: bool,
+    showing_capture: bool,
+    snapshots: snapshots::SnapshotsView,
 }
 
 impl Default for MyApp {
     fn default() -> Self {
-        let tree = DockState::new(vec!["music".to_owned(), "sounds".to_owned(), "images".to_owned(), "rbxm-files".to_owned(), "ktx-files".to_owned(), "settings".to_owned(), "logs".to_owned(), "about".to_owned()]);
+        // Asset tabs come from the signature registry, music is read from its own directory
+        let mut tabs = vec!["music".to_owned()];
+        tabs.extend(logic::get_categories());
+        tabs.extend(["snapshots".to_owned(), "settings".to_owned(), "logs".to_owned(), "about".to_owned()]);
+        let tree = DockState::new(tabs);
 
         // Tab map for keyboard navigation
         let mut tab_map = HashMap::new();
@@ -955,6 +1495,11 @@ impl Default for MyApp {
             locale: locale::get_locale(None),
             asset_context_menu_open: None,
             copying: false,
+            player: player::Player::default(),
+            similar_to: None,
+            showing_duplicates: false,
+            showing_capture: false,
+            snapshots: snapshots::SnapshotsView::default(),
         }
     }
 }
@@ -1080,6 +1625,8 @@ pub fn gui_setup(cc: &eframe::CreationContext<'_>) {
         "light" => cc.egui_ctx.set_theme(egui::Theme::Light),
         _ => () // Use system default theme
     }
+
+    logic::watch_cache(config::get_config_bool("watch_cache").unwrap_or(false));
 }
 
 impl MyApp {
@@ -1107,6 +1654,7 @@ impl eframe::App for MyApp {
             }
         }
 
+        self.player.update(ctx);
 
         DockArea::new(&mut self.tree)
             .style(Style::from_egui(ctx.style().as_ref()))
@@ -1126,6 +1674,11 @@ impl eframe::App for MyApp {
                 locale: &mut self.locale,
                 asset_context_menu_open: &mut self.asset_context_menu_open,
                 copying: &mut self.copying,
+                player: &mut self.player,
+                similar_to: &mut self.similar_to,
+                showing_duplicates: &mut self.showing_duplicates,
+                showing_capture: &mut self.showing_capture,
+                snapshots: &mut self.snapshots,
             });
         
         {
@@ -1133,6 +1686,10 @@ impl eframe::App for MyApp {
             if logic::get_request_repaint() {
                 ctx.request_repaint_after(Duration::from_millis(250)); // Delay added here to prevent refreshes from stopping
             }
+            // The watcher can change the list while nothing else is happening
+            if config::get_config_bool("watch_cache").unwrap_or(false) {
+                ctx.request_repaint_after(Duration::from_secs(1));
+            }
         }
     }
 }
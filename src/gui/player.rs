@@ -0,0 +1,234 @@
+// Built-in player for sounds, so they don't have to be opened in another program
+//
+// Sounds are decoded up front on another thread, the audio device reads the samples through
+// a Playback. Without the audio-playback feature there is no output, but the waveform still works.
+use eframe::egui;
+use fluent_bundle::{FluentBundle, FluentResource};
+use std::path::PathBuf;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crate::{locale, log, logic};
+use logic::pcm::{Pcm, Playback};
+
+const WAVEFORM_COLUMNS: usize = 1024;
+const WAVEFORM_HEIGHT: f32 = 80.0;
+
+#[cfg(feature = "audio-playback")]
+type Stream = cpal::Stream;
+#[cfg(not(feature = "audio-playback"))]
+type Stream = ();
+
+struct Loaded {
+    name: String,
+    playback: Arc<Mutex<Playback>>,
+    waveform: Vec<[f32; 2]>,
+    duration: Duration,
+    /// Playing stops when this is dropped, None if there is no audio output
+    stream: Option<Stream>,
+}
+
+/// Sound that is being decoded
+struct Loading {
+    name: String,
+    samples: mpsc::Receiver<Result<Pcm, String>>,
+    /// Cache directory and tab to open the sound in another program from if it can't be heard
+    fallback: Option<(PathBuf, String)>,
+}
+
+#[derive(Default)]
+pub struct Player {
+    loading: Option<Loading>,
+    loaded: Option<Loaded>,
+    /// Asset that failed to decode and why
+    error: Option<(String, String)>,
+}
+
+#[cfg(feature = "audio-playback")]
+fn build_stream<T: cpal::SizedSample + cpal::FromSample<f32>>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    playback: Arc<Mutex<Playback>>,
+) -> Result<Stream, String> {
+    use cpal::traits::DeviceTrait;
+    let channels = config.channels as usize;
+    let sample_rate = config.sample_rate.0;
+    let mut buffer = Vec::new();
+    device.build_output_stream(
+        config,
+        move |output: &mut [T], _| {
+            buffer.resize(output.len(), 0.0);
+            playback.lock().unwrap().fill(&mut buffer, channels, sample_rate);
+            for (output, sample) in output.iter_mut().zip(&buffer) {
+                *output = T::from_sample(*sample);
+            }
+        },
+        |e| log::error(&format!("Audio output error: {}", e)),
+        None,
+    ).map_err(|e| e.to_string())
+}
+
+#[cfg(feature = "audio-playback")]
+fn open_stream(playback: Arc<Mutex<Playback>>) -> Result<Stream, String> {
+    use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+    let device = cpal::default_host().default_output_device().ok_or("No audio output device")?;
+    let config = device.default_output_config().map_err(|e| e.to_string())?;
+    let stream = match config.sample_format() {
+        cpal::SampleFormat::F32 => build_stream::<f32>(&device, &config.into(), playback),
+        cpal::SampleFormat::I16 => build_stream::<i16>(&device, &config.into(), playback),
+        cpal::SampleFormat::U16 => build_stream::<u16>(&device, &config.into(), playback),
+        format => Err(format!("Unsupported sample format {}", format)),
+    }?;
+    stream.play().map_err(|e| e.to_string())?;
+    Ok(stream)
+}
+
+#[cfg(not(feature = "audio-playback"))]
+fn open_stream(_playback: Arc<Mutex<Playback>>) -> Result<Stream, String> {
+    Err("RoExtract was built without the audio-playback feature".to_owned())
+}
+
+impl Player {
+    fn is_loaded(&self, name: &str) -> bool {
+        self.loaded.as_ref().is_some_and(|loaded| loaded.name == name)
+    }
+
+    fn is_loading(&self, name: &str) -> bool {
+        self.loading.as_ref().is_some_and(|loading| loading.name == name)
+    }
+
+    /// Start decoding a sound, it plays once the samples arrive. With `open_elsewhere` it is opened in
+    /// another program instead if it can't be decoded or heard.
+    pub fn open(&mut self, cache_directory: PathBuf, name: &str, mode: &str, open_elsewhere: bool) {
+        self.loaded = None; // Stop the sound that is playing
+        self.error = None;
+        let (sender, samples) = mpsc::channel();
+        let (path, thread_mode) = (cache_directory.join(name), mode.to_owned());
+        thread::spawn(move || {
+            let _ = sender.send(logic::decode_sound(path, &thread_mode)); // Nothing is waiting if another sound was opened
+        });
+        self.loading = Some(Loading {
+            name: name.to_owned(),
+            samples,
+            fallback: open_elsewhere.then(|| (cache_directory, mode.to_owned())),
+        });
+    }
+
+    /// Check if the sound being decoded is ready, called every frame
+    pub fn update(&mut self, ctx: &egui::Context) {
+        let Some(loading) = &self.loading else { return };
+        let result = match loading.samples.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => {
+                ctx.request_repaint_after(Duration::from_millis(50));
+                return
+            }
+            Err(mpsc::TryRecvError::Disconnected) => Err("The decoder stopped".to_owned()),
+        };
+        let Some(loading) = self.loading.take() else { return };
+        if !self.start(&loading.name, result) {
+            if let Some((cache_directory, mode)) = loading.fallback {
+                super::open_in_other_program(cache_directory, loading.name, &mode);
+            }
+        }
+    }
+
+    /// Start playing decoded samples, returns false if they couldn't be decoded or heard
+    fn start(&mut self, name: &str, result: Result<Pcm, String>) -> bool {
+        match result {
+            Ok(pcm) => {
+                let pcm = Arc::new(pcm);
+                let playback = Arc::new(Mutex::new(Playback::new(pcm.clone())));
+                let stream = match open_stream(playback.clone()) {
+                    Ok(stream) => Some(stream),
+                    Err(e) => {
+                        log::warn(&format!("Failed to open the audio output: {}", e));
+                        None
+                    }
+                };
+                let audible = stream.is_some();
+                if audible {
+                    playback.lock().unwrap().play();
+                }
+                self.loaded = Some(Loaded {
+                    name: name.to_owned(),
+                    waveform: pcm.waveform(WAVEFORM_COLUMNS),
+                    duration: pcm.duration(),
+                    playback,
+                    stream,
+                });
+                self.error = None;
+                audible
+            }
+            Err(e) => {
+                log::warn(&format!("Failed to decode {}: {}", name, e));
+                self.error = Some((name.to_owned(), e));
+                false
+            }
+        }
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui, locale: &FluentBundle<Arc<FluentResource>>, cache_directory: PathBuf, name: &str, mode: &str) {
+        if !self.is_loaded(name) {
+            if let Some((error_name, error)) = &self.error {
+                if error_name == name {
+                    let mut args = fluent_bundle::FluentArgs::new();
+                    args.set("error", error.clone());
+                    ui.label(locale::get_message(locale, "error-decoding-sound", Some(&args)));
+                    return
+                }
+            }
+            if self.is_loading(name) {
+                ui.spinner();
+            } else if ui.button(locale::get_message(locale, "button-play", None)).clicked() {
+                self.open(cache_directory, name, mode, false);
+            }
+            return
+        }
+        let Some(loaded) = &self.loaded else { return };
+        let mut playback = loaded.playback.lock().unwrap();
+
+        ui.horizontal(|ui| {
+            let message = if playback.playing { "button-pause" } else { "button-play" };
+            let button = egui::Button::new(locale::get_message(locale, message, None));
+            if ui.add_enabled(loaded.stream.is_some(), button).clicked() {
+                if playback.playing {
+                    playback.playing = false;
+                } else {
+                    playback.play();
+                }
+            }
+            ui.label(format!("{} / {}", super::format_duration(playback.position()), super::format_duration(loaded.duration)));
+        });
+        if loaded.stream.is_none() {
+            ui.label(locale::get_message(locale, "error-audio-output", None));
+        }
+
+        // Waveform, clicking or dragging on it seeks
+        let (rect, response) = ui.allocate_exact_size(egui::vec2(ui.available_width(), WAVEFORM_HEIGHT), egui::Sense::click_and_drag());
+        if let Some(pointer) = response.interact_pointer_pos() {
+            let fraction = ((pointer.x - rect.left()) / rect.width()).clamp(0.0, 1.0);
+            playback.seek(loaded.duration.mul_f32(fraction));
+        }
+        let played = playback.position().as_secs_f32() / loaded.duration.as_secs_f32().max(f32::EPSILON);
+
+        let visuals = ui.visuals();
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 2.0, visuals.extreme_bg_color);
+        let columns = loaded.waveform.len() as f32;
+        let width = (rect.width() / columns).max(1.0);
+        for (i, [low, high]) in loaded.waveform.iter().enumerate() {
+            let x = rect.left() + (i as f32 + 0.5) / columns * rect.width();
+            let top = rect.center().y - high.clamp(-1.0, 1.0) * rect.height() / 2.0;
+            let bottom = (rect.center().y - low.clamp(-1.0, 1.0) * rect.height() / 2.0).max(top + 1.0);
+            let colour = if (i as f32 / columns) < played { visuals.selection.bg_fill } else { visuals.weak_text_color() };
+            painter.line_segment([egui::pos2(x, top), egui::pos2(x, bottom)], egui::Stroke::new(width, colour));
+        }
+        painter.vline(rect.left() + played * rect.width(), rect.y_range(), egui::Stroke::new(1.0, visuals.strong_text_color()));
+
+        if playback.playing {
+            ui.ctx().request_repaint_after(Duration::from_millis(33)); // Move the cursor along
+        }
+    }
+}
@@ -53,7 +53,7 @@ pub fn actions(ui: &mut egui::Ui, locale: &FluentBundle<Arc<FluentResource>>) {
     
             // If the user provides a directory, the program will extract the assets to that directory
             if let Some(path) = option_path {
-                logic::extract_all( path, false, config::get_config_bool("use_alias").unwrap_or(false))
+                logic::extract_all( path, false, config::get_config_bool("use_alias").unwrap_or(false), config::get_config_bool("extract_unique_only").unwrap_or(false))
             }
         }
     }
@@ -146,6 +146,20 @@ pub fn behavior(ui: &mut egui::Ui, locale: &FluentBundle<Arc<FluentResource>>) {
     ui.checkbox(&mut use_alias, locale::get_message(locale, "refresh-before-extract", None));
     config::set_config_value("refresh_before_extract", use_alias.into());
 
+    let mut detect_duplicates = config::get_config_bool("detect_duplicates").unwrap_or(false);
+    ui.checkbox(&mut detect_duplicates, locale::get_message(locale, "detect-duplicates", None));
+    config::set_config_value("detect_duplicates", detect_duplicates.into());
+
+    let mut watch_cache = config::get_config_bool("watch_cache").unwrap_or(false);
+    if ui.checkbox(&mut watch_cache, locale::get_message(locale, "watch-cache", None)).changed() {
+        logic::watch_cache(watch_cache);
+    }
+    config::set_config_value("watch_cache", watch_cache.into());
+
+    let mut extract_unique_only = config::get_config_bool("extract_unique_only").unwrap_or(false);
+    ui.checkbox(&mut extract_unique_only, locale::get_message(locale, "extract-unique-only", None));
+    config::set_config_value("extract_unique_only", extract_unique_only.into());
+
     let mut use_topbar_buttons = config::get_config_bool("use_topbar_buttons").unwrap_or(true);
     ui.checkbox(&mut use_topbar_buttons, locale::get_message(locale, "use-topbar-buttons", None));
     config::set_config_value("use_topbar_buttons", use_topbar_buttons.into());
@@ -154,10 +168,26 @@ pub fn behavior(ui: &mut egui::Ui, locale: &FluentBundle<Arc<FluentResource>>) {
     ui.checkbox(&mut display_image_preview, locale::get_message(locale, "button-display-image-preview", None));
     config::set_config_value("display_image_preview", display_image_preview.into());
 
+    let mut animate_previews = config::get_config_bool("animate_previews").unwrap_or(true);
+    if ui.checkbox(&mut animate_previews, locale::get_message(locale, "animate-previews", None)).changed() {
+        super::clear_image_previews(); // Load them again with or without their frames
+    }
+    config::set_config_value("animate_previews", animate_previews.into());
+
     let mut image_preview_size = config::get_config_u64("image_preview_size").unwrap_or(128);
     ui.add(egui::widgets::Slider::new(&mut image_preview_size, (16 as u64)..=(512 as u64))
     .text(locale::get_message(locale, "input-preview-size", None)));
     config::set_config_value("image_preview_size", image_preview_size.into());
+
+    let mut similar_images_threshold = config::get_config_u64("similar_images_threshold").unwrap_or(8);
+    ui.add(egui::widgets::Slider::new(&mut similar_images_threshold, 0..=32)
+    .text(locale::get_message(locale, "input-similar-images-threshold", None)));
+    config::set_config_value("similar_images_threshold", similar_images_threshold.into());
+
+    let mut worker_threads = config::get_config_u64("worker_threads").unwrap_or(0);
+    ui.add(egui::widgets::Slider::new(&mut worker_threads, 0..=64)
+    .text(locale::get_message(locale, "input-worker-threads", None)));
+    config::set_config_value("worker_threads", worker_threads.into());
     
 }
 
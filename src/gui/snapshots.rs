@@ -0,0 +1,104 @@
+use crate::{config, locale, logic};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use fluent_bundle::{FluentBundle, FluentResource, FluentArgs};
+
+/// What the snapshots tab remembers between frames
+#[derive(Default)]
+pub struct SnapshotsView {
+    name: String,
+    old: Option<String>,
+    new: Option<String>,
+    diff: Option<Result<BTreeMap<String, logic::CategoryDiff>, String>>,
+}
+
+fn snapshot_combo_box(ui: &mut egui::Ui, id: &str, label: String, names: &[String], selected: &mut Option<String>) {
+    ui.label(label);
+    egui::ComboBox::from_id_salt(id)
+    .selected_text(selected.clone().unwrap_or_default())
+    .show_ui(ui, |ui| {
+        for name in names {
+            ui.selectable_value(selected, Some(name.clone()), name);
+        }
+    });
+}
+
+pub fn save(ui: &mut egui::Ui, locale: &FluentBundle<Arc<FluentResource>>, view: &mut SnapshotsView) {
+    ui.heading(locale::get_message(locale, "snapshots", None));
+    ui.label(locale::get_message(locale, "snapshots-description", None));
+
+    ui.horizontal(|ui| {
+        ui.add(egui::TextEdit::singleline(&mut view.name).hint_text(locale::get_message(locale, "input-snapshot-name", None)));
+        if ui.button(locale::get_message(locale, "button-save-snapshot", None)).clicked() && !view.name.trim().is_empty() {
+            logic::save_snapshot(view.name.trim().to_owned(), false);
+            view.name.clear();
+        }
+    });
+}
+
+pub fn compare(ui: &mut egui::Ui, locale: &FluentBundle<Arc<FluentResource>>, view: &mut SnapshotsView) {
+    ui.separator();
+    ui.heading(locale::get_message(locale, "compare-snapshots", None));
+
+    let names = logic::get_snapshot_names();
+    if names.is_empty() {
+        ui.label(locale::get_message(locale, "no-snapshots", None));
+        return
+    }
+
+    ui.horizontal(|ui| {
+        snapshot_combo_box(ui, "old_snapshot", locale::get_message(locale, "snapshot-old", None), &names, &mut view.old);
+        snapshot_combo_box(ui, "new_snapshot", locale::get_message(locale, "snapshot-new", None), &names, &mut view.new);
+
+        let selected = view.old.clone().zip(view.new.clone());
+        if ui.add_enabled(selected.is_some(), egui::Button::new(locale::get_message(locale, "button-compare-snapshots", None))).clicked() {
+            if let Some((old, new)) = selected {
+                view.diff = Some(logic::diff_snapshots(&old, &new));
+            }
+        }
+        if let Some(old) = view.old.clone() {
+            let mut args = FluentArgs::new();
+            args.set("name", old.clone());
+            if ui.button(locale::get_message(locale, "button-delete-snapshot", Some(&args))).clicked() {
+                logic::delete_snapshot(&old);
+                view.old = None;
+                view.diff = None;
+            }
+        }
+    });
+
+    match &view.diff {
+        Some(Ok(diff)) if diff.is_empty() => {
+            ui.label(locale::get_message(locale, "snapshots-identical", None));
+        }
+        Some(Ok(diff)) => {
+            egui::ScrollArea::vertical().auto_shrink(false).show(ui, |ui| {
+                for (category, changes) in diff {
+                    let mut args = FluentArgs::new();
+                    args.set("category", locale::get_message(locale, category, None));
+                    args.set("added", changes.added.len());
+                    args.set("removed", changes.removed.len());
+                    args.set("modified", changes.modified.len());
+
+                    egui::CollapsingHeader::new(locale::get_message(locale, "snapshot-category-changes", Some(&args)))
+                    .id_salt(category)
+                    .show(ui, |ui| {
+                        for (message, names) in [("snapshot-added", &changes.added), ("snapshot-removed", &changes.removed), ("snapshot-modified", &changes.modified)] {
+                            if names.is_empty() {
+                                continue
+                            }
+                            ui.strong(locale::get_message(locale, message, None));
+                            for name in names {
+                                ui.label(config::get_asset_alias(name));
+                            }
+                        }
+                    });
+                }
+            });
+        }
+        Some(Err(e)) => {
+            ui.colored_label(egui::Color32::RED, e);
+        }
+        None => (),
+    }
+}
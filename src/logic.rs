@@ -1,7 +1,7 @@
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap, HashSet},
     fs,
-    io::Read,
+    io::{Read, Seek, SeekFrom},
     path::PathBuf,
     sync::{Arc, Mutex},
     thread,
@@ -9,9 +9,41 @@ use std::{
 };
 use fluent_bundle::{FluentArgs, FluentBundle, FluentResource};
 use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
 
 use crate::{config, locale, log};
 
+pub mod animation;
+mod audio;
+mod audio_export;
+mod basis;
+mod convert;
+mod decompress;
+mod http_cache;
+mod image_hash;
+mod image_info;
+mod ktx;
+mod ktx2;
+mod mesh;
+mod mesh_export;
+pub mod pcm;
+pub mod rbxm;
+mod rbxmx;
+mod render;
+mod scan_index;
+mod signatures;
+mod snapshot;
+mod stable_hash;
+mod texture;
+mod watcher;
+mod worker_pool;
+
+use http_cache::CacheEntry;
+use signatures::Signature;
+pub use audio::AudioInfo;
+pub use image_info::ImageInfo;
+pub use snapshot::CategoryDiff;
+
 // Define mutable static values
 lazy_static! {
     static ref TEMP_DIRECTORY: Mutex<Option<tempfile::TempDir>> = Mutex::new(None);
@@ -25,62 +57,71 @@ lazy_static! {
     static ref STOP_LIST_RUNNING: Mutex<bool> = Mutex::new(false);
 
     static ref FILTERED_FILE_LIST: Mutex<Vec<AssetInfo>> = Mutex::new(Vec::new());
+    static ref FILTER_QUERY: Mutex<String> = Mutex::new(String::new());
+    // Images that look like the one that was searched for, with their distance to it
+    static ref SIMILAR_FILE_LIST: Mutex<Vec<(AssetInfo, u32)>> = Mutex::new(Vec::new());
 
     static ref TASK_RUNNING: Mutex<bool> = Mutex::new(false); // Delete/extract
-
-    // File headers for each catagory
-    static ref HEADERS: Mutex<HashMap<String,[String;2]>> = {
-        let mut m = HashMap::new();
-        m.insert("sounds".to_owned(),[
-            "OggS".to_owned(),
-            "ID3".to_owned()
-            ]);
-        m.insert("images".to_owned(), [
-            "PNG".to_owned(),
-            "WEBP".to_owned()
-            ]);
-        m.insert("ktx-files".to_owned(), [
-            "KTX".to_owned(),
-            "".to_owned()
-            ]);
-        m.insert("rbxm-files".to_owned(), [
-            "<roblox!".to_owned(),
-            "".to_owned()
-            ]);
-        Mutex::new(m)
-    };
-
-    // File extention for headers
-    static ref EXTENTION: Mutex<HashMap<String, String>> = {
-        let mut m = HashMap::new();
-        m.insert("OggS".to_owned(), ".ogg".to_owned());
-        m.insert("ID3".to_owned(), ".mp3".to_owned());
-        m.insert("PNG".to_owned(), ".png".to_owned());
-        m.insert("WEBP".to_owned(), ".webp".to_owned());
-        m.insert("KTX".to_owned(), ".ktx".to_owned());
-        m.insert("<roblox!".to_owned(), ".rbxm".to_owned());
-        Mutex::new(m)
-    };
-
-    // Header offsets, headers that are not in this HashMap not be offset
-    // Offset will subtract from the found header.
-    static ref OFFSET: Mutex<HashMap<String, usize>> = {
-        let mut m = HashMap::new();
-        m.insert("PNG".to_owned(), 1);
-        m.insert("KTX".to_owned(), 1);
-        m.insert("WEBP".to_owned(), 8);
-        Mutex::new(m)
-    };
+    // Set when duplicates are shown without detecting them in the settings, only lasts until the program closes
+    static ref DETECT_DUPLICATES_THIS_SESSION: Mutex<bool> = Mutex::new(false);
+
+    // Loaded once, then kept up to date by every refresh
+    // Tab and directory of the files in FILE_LIST, so the watcher knows which files belong in it
+    static ref LISTED_TAB: Mutex<Option<(String, PathBuf)>> = Mutex::new(None);
+    static ref CACHE_WATCHER: Mutex<Option<watcher::CacheWatcher>> = Mutex::new(None);
+    // Assets the watcher found since the tab was opened
+    static ref NEW_ASSETS: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
+
+    // Cache as it was when the running capture started
+    static ref CAPTURE: Mutex<Option<snapshot::Snapshot>> = Mutex::new(snapshot::Snapshot::load(&config::get_data_file(CAPTURE_FILE)).ok());
+    // Cache as it was when the last capture started, the assets that changed since are the captured ones
+    static ref CAPTURED: Mutex<Option<snapshot::Snapshot>> = Mutex::new(None);
+
+    static ref SCAN_INDEX: Mutex<scan_index::ScanIndex> = Mutex::new(scan_index::ScanIndex::load(&config::get_data_file(SCAN_INDEX_FILE)));
 }
 
+// Amount of bytes read from the start of the body to identify a file
+const SNIFF_LEN: usize = 2048;
+// Most bytes that will be read to find the start of the body in a cache entry
+const MAX_HEAD_LEN: usize = 65536;
+// Largest body a compressed cache entry may decompress to
+const MAX_BODY_LEN: usize = 256 * 1024 * 1024;
+
+// Perceptual hashes of images, kept next to the config file
+const IMAGE_HASH_INDEX_FILE: &str = "RoExtract-image-hashes.json";
+// What every cached file was found to be, so refreshing only has to open new or changed files
+const SCAN_INDEX_FILE: &str = "RoExtract-scan-index.json";
+// Snapshot of the running capture, kept on disk so a capture started from the CLI can be stopped later
+const CAPTURE_FILE: &str = "RoExtract-capture.json";
+// Named snapshots of the cache, kept in a folder next to the config file
+const SNAPSHOT_DIRECTORY: &str = "RoExtract-snapshots";
+
 const DEFAULT_DIRECTORIES: [&str; 2] = ["%Temp%\\Roblox", "~/.var/app/org.vinegarhq.Sober/cache/sober"]; // For windows and linux (sober)
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AssetInfo {
     pub name: String,
     pub size: u64,
     pub last_modified: Option<SystemTime>,
-    pub real_asset: bool
+    pub real_asset: bool,
+    /// Length and format of sounds, read while refreshing
+    pub audio: Option<AudioInfo>,
+    /// Size and pixel format of images and textures, read while refreshing
+    pub image: Option<ImageInfo>,
+    /// Hash of the extracted asset, only read when duplicates are being detected
+    pub payload_hash: Option<u64>,
+    /// Position in the order the files were read in, so the list can go back to it after sorting
+    #[serde(skip)]
+    pub read_order: usize,
+}
+
+// What a worker found out about a file while refreshing
+struct ScannedFile {
+    name: String,
+    /// None when the file doesn't belong in the tab
+    asset: Option<AssetInfo>,
+    /// Size and modification time to store in the scan index, None when the index was already up to date
+    index_key: Option<(u64, Option<SystemTime>)>,
 }
 
 // Define local functions
@@ -98,13 +139,14 @@ fn update_progress(value: f32) {
     *request = true;
 }
 
-fn update_file_list(value: AssetInfo, cli_list_mode: bool) {
+fn update_file_list(mut value: AssetInfo, cli_list_mode: bool) {
     // cli_list_mode will print out to console
     // It is done this way so it can read files and print to console in the same stage
     if cli_list_mode {
         println!("{}", value.name);
     }
     let mut file_list = FILE_LIST.lock().unwrap();
+    value.read_order = file_list.len();
     file_list.push(value)
 }
 
@@ -113,68 +155,109 @@ fn clear_file_list() {
     *file_list = Vec::new()
 }
 
-fn bytes_search(haystack: Vec<u8>, needle: &[u8]) -> Option<usize> {
-    let len = needle.len();
-    if len > 0 {
-        haystack.windows(len).position(|window| window == needle)
-    } else {
-        None
+/// Amount of threads that read and write files at once, 0 in the config uses every core
+fn worker_count() -> usize {
+    match config::get_config_u64("worker_threads").unwrap_or(0) {
+        0 => worker_pool::default_workers(),
+        workers => workers as usize,
     }
 }
 
-fn bytes_contains(haystack: &[u8], needle: &[u8]) -> bool {
-    let len = needle.len();
-    if len > 0 {
-        if needle == b"ID3" {
-            let bin_type = haystack.windows(7).any(|window| window == b"binary/");
-            let mp3_header = haystack.windows(len).any(|window| window == needle);
-            mp3_header && bin_type // Only allow mp3 if type is binary as that is what the client uses
-        } else {
-            haystack.windows(len).any(|window| window == needle)
+fn read_head(path: &PathBuf) -> std::io::Result<Vec<u8>> {
+    let mut file = fs::File::open(path)?;
+
+    // Reading the first 2048 bytes of the file
+    let mut buffer = Vec::new();
+    (&mut file).take(SNIFF_LEN as u64).read_to_end(&mut buffer)?;
+
+    // Long urls or headers can push the start of the body past the first read
+    if http_cache::is_cache_entry(&buffer) && buffer.len() == SNIFF_LEN {
+        let wanted = match CacheEntry::parse(&buffer) {
+            Ok(entry) => entry.body_offset + SNIFF_LEN,
+            Err(_) => MAX_HEAD_LEN,
+        };
+        if wanted > buffer.len() {
+            file.take((wanted - buffer.len()) as u64).read_to_end(&mut buffer)?;
         }
-    } else {
-        false
     }
 
+    Ok(buffer)
 }
 
-fn find_header(mode: &str, bytes: Vec<u8>) -> String {
-    // Get headers and offsets, they will be used later
-    let all_headers = {
-        HEADERS.lock().unwrap().clone()
+fn decoded_body(entry: &CacheEntry, bytes: &[u8], limit: decompress::Limit) -> Vec<u8> {
+    let body = entry.body(bytes);
+    let encodings = match entry.header("content-encoding") {
+        Some(header) => match decompress::parse_content_encoding(header) {
+            Ok(encodings) => encodings,
+            Err(e) => {
+                log::warn(&format!("{}: {}", entry.url, e));
+                return body.to_vec()
+            }
+        },
+        // Some entries don't store the header, detect it from the body instead
+        None => decompress::sniff_encoding(body).into_iter().collect(),
     };
 
-    // Get the header for the current mode
-    let option_headers = all_headers.get(mode);
+    if encodings.is_empty() {
+        return body.to_vec()
+    }
 
-    if let Some(headers) = option_headers {
-        // Itearte through headers to find the correct one for this file.
-        for header in headers {
-            if bytes_contains(&bytes, header.as_bytes()) {
-                return header.to_owned()
+    match decompress::decode(body, &encodings, limit) {
+        Ok(decoded) => decoded,
+        Err(e) => {
+            log::warn(&format!("Failed to decompress {}: {}", entry.url, e));
+            body.to_vec()
+        }
+    }
+}
+
+fn identify(signatures: &[Signature], bytes: &[u8]) -> Option<Signature> {
+    match CacheEntry::parse(bytes) {
+        Ok(entry) => {
+            if !entry.is_success() {
+                return None // Redirects and errors don't contain assets
             }
+            // The stored content type is the most reliable, it decides the category when it is known
+            if let Some(content_type) = entry.header("content-type") {
+                if let Some(signature) = signatures::find_content_type(content_type) {
+                    return signatures.iter().find(|s| s.name == signature.name).cloned()
+                }
+            }
+
+            // Unknown or generic content type, fall back to the magic bytes.
+            // Only look at the body so urls and headers can't cause false positives
+            let headers = entry.header_text();
+            let body = decoded_body(&entry, bytes, decompress::Limit::Prefix(SNIFF_LEN));
+            signatures.iter().find(|signature| signature.matches_body(headers.as_bytes(), &body)).cloned()
         }
+        // Not a cache entry, scan for the magic bytes instead
+        Err(_) => signatures.iter().find(|signature| signature.matches(bytes)).cloned(),
     }
-    return "INVALID".to_owned()
 }
 
-fn extract_bytes(header: &str, bytes: Vec<u8>) -> Vec<u8> {
-    // Get offsets for headers
-    let offsets = {
-        OFFSET.lock().unwrap().clone()
-    };
+fn find_header(mode: &str, bytes: &[u8]) -> Option<Signature> {
+    // Find the signature for the current mode
+    identify(&signatures::get_category_signatures(mode), bytes)
+}
 
-    // Find the header in the file
-    if let Some(mut index) = bytes_search(bytes.clone(), header.as_bytes()) {
-        // Found the header, extract from the bytes
-        if let Some(offset) = offsets.get(header) {
-            // Apply offset to index if the offset exists
-            index -= *offset;
+fn extract_bytes(signature: Option<&Signature>, bytes: Vec<u8>) -> Vec<u8> {
+    // Use the body boundaries from the cache entry if it has them
+    if let Ok(entry) = CacheEntry::parse(&bytes) {
+        let available = entry.body(&bytes).len();
+        if available < entry.body_len {
+            log::warn(&format!("Cache entry for {} is missing {} bytes", entry.url, entry.body_len - available));
         }
-        // Return all the bytes after the found header index
-        return bytes[index..].to_vec()
+        return decoded_body(&entry, &bytes, decompress::Limit::Max(MAX_BODY_LEN))
+    }
+
+    if let Some(signature) = signature {
+        // Find the start of the real file
+        if let Some(index) = signature.find_start(&bytes) {
+            // Return all the bytes after the found header index
+            return bytes[index..].to_vec()
+        }
+        log::warn("Failed to extract a file!");
     }
-    log::warn("Failed to extract a file!");
     // Return bytes instead if this fails
     return bytes
 }
@@ -192,7 +275,11 @@ fn create_asset_info(path: &PathBuf, file: &str) -> AssetInfo {
                 name: file.to_string(),
                 size: size,
                 last_modified: last_modified,
-                real_asset: true
+                real_asset: true,
+                audio: None,
+                image: None,
+                payload_hash: None,
+                read_order: 0,
             }
         }
         Err(e) => {
@@ -201,10 +288,147 @@ fn create_asset_info(path: &PathBuf, file: &str) -> AssetInfo {
                 name: file.to_string(),
                 size: 0,
                 last_modified: None,
-                real_asset: true
+                real_asset: true,
+                audio: None,
+                image: None,
+                payload_hash: None,
+                read_order: 0,
+            }
+        }
+    }
+}
+
+/// Find the length of a sound from the start and end of its body, the whole file is only read when they aren't enough
+fn read_audio_info(path: &PathBuf, signature: Option<&Signature>) -> Option<AudioInfo> {
+    match read_sound_excerpt(path, signature) {
+        Ok(Some((head, tail, len))) => {
+            if let Ok(info) = audio::probe_excerpt(&head, &tail, len) {
+                return info
+            }
+        }
+        Ok(None) => (), // Short enough to read all of it, or compressed
+        Err(e) => {
+            log::warn(&format!("Failed to read sound: {}", e));
+            return None
+        }
+    }
+    match fs::read(path) {
+        Ok(bytes) => audio::probe(&extract_bytes(signature, bytes)),
+        Err(e) => {
+            log::warn(&format!("Failed to read sound: {}", e));
+            None
+        }
+    }
+}
+
+// First and last bytes of the body of a sound, and the length of the body
+type SoundExcerpt = (Vec<u8>, Vec<u8>, usize);
+
+/// None if the body is short or compressed, as it can't be read from the middle then
+fn read_sound_excerpt(path: &PathBuf, signature: Option<&Signature>) -> std::io::Result<Option<SoundExcerpt>> {
+    let mut file = fs::File::open(path)?;
+    let file_len = file.metadata()?.len() as usize;
+    if file_len <= audio::EXCERPT_LEN * 2 {
+        return Ok(None)
+    }
+    let mut head = Vec::new();
+    (&mut file).take(audio::EXCERPT_LEN as u64).read_to_end(&mut head)?;
+
+    let (start, end) = match CacheEntry::parse(&head) {
+        Ok(entry) => {
+            let compressed = match entry.header("content-encoding") {
+                Some(header) => !decompress::parse_content_encoding(header).is_ok_and(|encodings| encodings.is_empty()),
+                None => decompress::sniff_encoding(entry.body(&head)).is_some(),
+            };
+            if compressed {
+                return Ok(None)
+            }
+            (entry.body_offset, entry.body_offset.saturating_add(entry.body_len).min(file_len))
+        }
+        // Like extract_bytes, files without a cache entry start at their magic bytes if they are found
+        Err(_) => (signature.and_then(|signature| signature.find_start(&head)).unwrap_or(0), file_len),
+    };
+    if end.saturating_sub(start) <= audio::EXCERPT_LEN * 2 || start >= head.len() {
+        return Ok(None)
+    }
+
+    let mut tail = vec![0; audio::EXCERPT_LEN];
+    file.seek(SeekFrom::Start((end - audio::EXCERPT_LEN) as u64))?;
+    file.read_exact(&mut tail)?;
+    head.drain(..start);
+    Ok(Some((head, tail, end - start)))
+}
+
+/// Read the size and format of an image from the head of its file, the headers are enough
+fn read_image_info(path: &PathBuf, head: &[u8], signature: &Signature) -> Option<ImageInfo> {
+    let entry = match CacheEntry::parse(head) {
+        Ok(entry) => entry,
+        Err(_) => return image_info::probe(&head[signature.find_start(head)?..]),
+    };
+    let info = image_info::probe(&decoded_body(&entry, head, decompress::Limit::Prefix(SNIFF_LEN)));
+    // The first compressed block can be larger than the head, then the whole file is needed
+    let compressed = entry.header("content-encoding").is_some() || decompress::sniff_encoding(entry.body(head)).is_some();
+    if info.is_none() && compressed {
+        return match fs::read(path) {
+            Ok(bytes) => image_info::probe(&extract_bytes(Some(signature), bytes)),
+            Err(e) => {
+                log::warn(&format!("Failed to read image: {}", e));
+                None
             }
         }
     }
+    info
+}
+
+/// Hash of the asset without the cache entry around it, so the same asset in different entries matches
+fn read_payload_hash(path: &PathBuf, mode: &str) -> Option<u64> {
+    match fs::read(path) {
+        Ok(bytes) => {
+            let header = find_header(mode, &bytes);
+            Some(stable_hash::hash(&extract_bytes(header.as_ref(), bytes)))
+        }
+        Err(e) => {
+            log::warn(&format!("Failed to read asset: {}", e));
+            None
+        }
+    }
+}
+
+/// Paths to extract each asset to, given its directory and name. Assets can share an alias,
+/// the later ones get a number so the workers don't overwrite each other's files
+fn destination_paths<'a>(assets: impl IntoIterator<Item = (PathBuf, &'a str)>, use_alias: bool) -> Vec<PathBuf> {
+    let mut used_paths = HashSet::new();
+    assets.into_iter().map(|(directory, name)| {
+        let name = if use_alias {
+            config::get_asset_alias(name)
+        } else {
+            name.to_owned()
+        };
+        let mut path = directory.join(&name);
+        let mut number = 1;
+        while !used_paths.insert(path.clone()) {
+            number += 1;
+            path = directory.join(format!("{} ({})", name, number));
+        }
+        path
+    }).collect()
+}
+
+/// Keep only the first copy of each asset, in order, for extracting unique assets.
+/// The contents are hashed by the workers, hashes that were already extracted are skipped.
+fn first_copies<T: Sync>(items: Vec<T>, extracted: &mut HashSet<u64>, locale: &FluentBundle<Arc<FluentResource>>, hash: impl Fn(&T) -> Option<u64> + Sync) -> Vec<T> {
+    let total = items.len();
+    let mut keep = Vec::with_capacity(total);
+    worker_pool::run(&items, worker_count(), hash, |position, hash| {
+        let mut args = FluentArgs::new();
+        args.set("item", position + 1);
+        args.set("total", total);
+        update_status(locale::get_message(locale, "checking-duplicates", Some(&args)));
+
+        keep.push(hash.is_none_or(|hash| extracted.insert(hash)));
+        true
+    });
+    items.into_iter().zip(keep).filter_map(|(item, keep)| keep.then_some(item)).collect()
 }
 
 fn create_no_files(locale: &FluentBundle<Arc<FluentResource>>) -> AssetInfo {
@@ -212,7 +436,11 @@ fn create_no_files(locale: &FluentBundle<Arc<FluentResource>>) -> AssetInfo {
         name: locale::get_message(&locale, "no-files", None),
         size: 0,
         last_modified: None,
-        real_asset: false
+        real_asset: false,
+        audio: None,
+        image: None,
+        payload_hash: None,
+        read_order: 0,
     }
 }
 
@@ -412,45 +640,105 @@ pub fn delete_all_directory_contents(dir: PathBuf) {
     }
 }
 
-pub fn refresh(dir: PathBuf, mode: String, cli_list_mode: bool, yield_for_thread: bool) {
-    // Get headers for use later
-    let all_headers = {
-        HEADERS.lock().unwrap().clone()
-    };
+/// Find out if a file belongs in the tab, with its metadata, reusing what the scan index knows about it
+fn scan_file(path: &PathBuf, mode: &str, signatures: &[Signature], detect_duplicates: bool, index: &scan_index::ScanIndex) -> std::io::Result<ScannedFile> {
+    // Safely unwrap the file name
+    let filename = path.file_name().ok_or_else(|| std::io::Error::new(
+        std::io::ErrorKind::Other,
+        "No filename!"
+    ))?.to_string_lossy().to_string();
+
+    let mut asset_info = create_asset_info(path, &filename);
+    let (size, modified) = (asset_info.size, asset_info.last_modified);
+
+    // Use what was found last time unless the file changed, or it still has to be hashed
+    let cached = index.get(mode, &filename, size, modified)
+        .filter(|asset| !detect_duplicates || asset.is_none_or(|asset| asset.payload_hash.is_some()))
+        .map(|asset| asset.cloned());
+    if let Some(asset) = cached {
+        return Ok(ScannedFile { name: filename, asset, index_key: None })
+    }
 
-    let headers = if let Some(value) = all_headers.get(&mode) {
-        value.clone()
+    let asset = if mode == "music" {
+        asset_info.audio = read_audio_info(path, None);
+        if detect_duplicates {
+            asset_info.payload_hash = read_payload_hash(path, mode);
+        }
+        Some(asset_info)
     } else {
-        return;
+        let buffer = read_head(path)?;
+
+        // Add it to the list if any signature of this mode matches the file.
+        identify(signatures, &buffer).map(|signature| {
+            if mode == "sounds" {
+                asset_info.audio = read_audio_info(path, Some(&signature));
+            } else if mode == "images" || mode == "ktx-files" {
+                asset_info.image = read_image_info(path, &buffer, &signature);
+            }
+            if detect_duplicates {
+                asset_info.payload_hash = read_payload_hash(path, mode);
+            }
+            asset_info
+        })
     };
-    let handle = thread::spawn(move || {
-        // Get locale for localised status messages
-        let locale = locale::get_locale(None);
-        // This loop here is to make it wait until it is not running, and to set the STOP_LIST_RUNNING to true if it is running to make the other thread
+    Ok(ScannedFile { name: filename, asset, index_key: Some((size, modified)) })
+}
+
+/// Marks a task that changes the file list as running until it is dropped, even if the task returns early
+struct ListTask;
+
+impl ListTask {
+    /// Wait until no other list task is running, `stop_other` asks the running one to stop first
+    fn acquire(stop_other: bool) -> ListTask {
         loop {
-            let running = {
-                let task = LIST_TASK_RUNNING.lock().unwrap();
-                task.clone()
-            };
-            if !running {
-                break // Break if not running
-            } else {
-                let mut stop = STOP_LIST_RUNNING.lock().unwrap(); // Tell the other thread to stop
-                *stop = true;
+            {
+                let mut task = LIST_TASK_RUNNING.lock().unwrap();
+                let mut stop = STOP_LIST_RUNNING.lock().unwrap();
+                if !*task {
+                    *task = true; // Tell other threads that a task is running
+                    if stop_other {
+                        *stop = false; // Disable the stop, otherwise this thread will stop!
+                    }
+                    break
+                } else if stop_other {
+                    *stop = true; // Tell the other thread to stop
+                }
             }
             thread::sleep(std::time::Duration::from_millis(10)); // Sleep for a bit to not be CPU intensive
         }
-        { 
-            let mut task = LIST_TASK_RUNNING.lock().unwrap();
-            *task = true; // Tell other threads that a task is running
-            let mut stop = STOP_LIST_RUNNING.lock().unwrap();
-            *stop = false; // Disable the stop, otherwise this thread will stop!
-        }
+        ListTask
+    }
+}
+
+impl Drop for ListTask {
+    fn drop(&mut self) {
+        *LIST_TASK_RUNNING.lock().unwrap() = false; // Allow other threads to run again
+    }
+}
+
+pub fn refresh(dir: PathBuf, mode: String, cli_list_mode: bool, yield_for_thread: bool) {
+    // Get signatures for use later
+    let signatures = signatures::get_category_signatures(&mode);
+
+    // Music is read from its own directory and has no signatures
+    if signatures.is_empty() && mode != "music" {
+        return;
+    }
+    let handle = thread::spawn(move || {
+        // Get locale for localised status messages
+        let locale = locale::get_locale(None);
+        // Tell the other task to stop and wait for it, the guard lets other tasks run again when this returns
+        let _task = ListTask::acquire(true);
 
         clear_file_list(); // Only list the files on the current tab
+        *LISTED_TAB.lock().unwrap() = Some((mode.clone(), dir.clone()));
+
+        // Files are only opened again if they changed since the last refresh
+        let mut index = SCAN_INDEX.lock().unwrap();
+        index.prepare(&mode, &dir, stable_hash::hash(format!("{:?}", signatures).as_bytes()));
 
         // Read directory
-        let entries: Vec<_> = match fs::read_dir(dir) {
+        let entries: Vec<_> = match fs::read_dir(&dir) {
             Ok(directory_read) => directory_read.collect(),
             Err(e) => {
                 // Abort operation, error occoured
@@ -460,25 +748,26 @@ pub fn refresh(dir: PathBuf, mode: String, cli_list_mode: bool, yield_for_thread
             }
         };
 
-        // Get amount and initlilize counter for progress
+        // Get amount for progress
         let total = entries.len();
-        let mut count = 0;
+
+        // Reading every asset in full is slow, so duplicates are only detected when asked for
+        let detect_duplicates = get_detect_duplicates();
 
         // Tell the user that there is no files to list to make it easy to tell that the program is working and it isn't broken
         if total == 0 {
             update_file_list(create_no_files(&locale), cli_list_mode);
         }
-            // Filter the files out
-            for entry in entries {
-                let stop = {
-                    let stop_task = STOP_LIST_RUNNING.lock().unwrap();
-                    stop_task.clone()
-                };
-                if stop {
-                    break // Stop if another thread requests to stop this task.
-                }
-                
-                count += 1; // Increase counter for progress
+
+        // Files are read by the workers, then listed here in the order they were found
+        let mut scanned_files = Vec::new();
+        let finished = {
+            let index = &index;
+            worker_pool::run(&entries, worker_count(), |entry| match entry {
+                Ok(entry) => scan_file(&entry.path(), &mode, &signatures, detect_duplicates, index),
+                Err(e) => Err(std::io::Error::new(e.kind(), e.to_string())),
+            }, |position, result| {
+                let count = position + 1;
                 update_progress(count as f32/total as f32); // Convert to f32 to allow floating point output
 
                 // Args for formatting
@@ -486,45 +775,12 @@ pub fn refresh(dir: PathBuf, mode: String, cli_list_mode: bool, yield_for_thread
                 args.set("item", count);
                 args.set("total", total);
 
-                let result = {
-                    let headers = &headers;
-                    let mode = &mode;
-                    move || -> std::io::Result<()> {
-                    let path = entry?.path();
-
-                    // Safely unwrap the file name
-                    let filename = path.file_name().ok_or_else(|| std::io::Error::new(
-                        std::io::ErrorKind::Other,
-                        "No filename!"
-                    ))?;
-
-                    if mode == "music" {
-                        update_file_list(create_asset_info(&path, &filename.to_string_lossy()), cli_list_mode);
-                    } else {
-                        let mut file = fs::File::open(&path)?;
-                    
-                        // Reading the first 2048 bytes of the file
-                        let mut buffer = vec![0; 2048];
-                        let bytes_read = file.read(&mut buffer)?;
-                        buffer.truncate(bytes_read);
-    
-                        // header.0 = header, header.1 = mode
-                        for header in headers {
-                            // Check if header is not empty before actually checking file
-                            if header != "" {
-                                // Add it to the list if the header is inside of the file.
-                                if bytes_contains(&buffer, header.as_bytes()) {                                        
-                                    update_file_list(create_asset_info(&path, &filename.to_string_lossy()), cli_list_mode);
-                                }
-                            }
-                        }    
-                    }
-
-
-                    Ok(())
-                }}();
                 match result {
-                    Ok(()) => {
+                    Ok(scanned) => {
+                        if let Some(asset) = &scanned.asset {
+                            update_file_list(asset.clone(), cli_list_mode);
+                        }
+                        scanned_files.push(scanned);
                         update_status(locale::get_message(&locale, "filtering-files", Some(&args)));
                     }
                     Err(e) => {
@@ -532,11 +788,31 @@ pub fn refresh(dir: PathBuf, mode: String, cli_list_mode: bool, yield_for_thread
                         update_status(locale::get_message(&locale, "failed-opening-file", Some(&args)));
                     }
                 }
+
+                // Stop if another thread requests to stop this task.
+                !*STOP_LIST_RUNNING.lock().unwrap()
+            })
+        };
+
+        // Remember what was found for the files that had to be read
+        let mut seen = HashSet::new();
+        for scanned in scanned_files {
+            if let Some((size, modified)) = scanned.index_key {
+                index.insert(&mode, &scanned.name, size, modified, scanned.asset);
             }
-        { 
-            let mut task = LIST_TASK_RUNNING.lock().unwrap();
-            *task = false; // Allow other threads to run again
+            seen.insert(scanned.name);
+        }
+
+        // Files that weren't reached yet when stopping may still be cached
+        if finished {
+            index.retain(&mode, |name| seen.contains(name));
         }
+        if let Err(e) = index.save(&config::get_data_file(SCAN_INDEX_FILE)) {
+            log::error(&format!("Failed to save the scan index: {}", e));
+        }
+        drop(index);
+
+        sort_file_list(&config::get_config_string("sort_by").unwrap_or("default".to_owned()));
         update_status(locale::get_message(&locale, "idling", None)); // Set the status back
     });
 
@@ -546,29 +822,48 @@ pub fn refresh(dir: PathBuf, mode: String, cli_list_mode: bool, yield_for_thread
     }
 }
 
-pub fn extract_file(file: PathBuf, mode: &str, destination: PathBuf, add_extention: bool) -> PathBuf {
+/// Returns where the asset was written, nothing is written if it couldn't be converted to `convert_to`
+pub fn extract_file(file: PathBuf, mode: &str, destination: PathBuf, add_extention: bool, convert_to: Option<&str>) -> Result<PathBuf, String> {
     let mut destination = destination.clone(); // Get own mutable destination
     match fs::metadata(file.clone()) {
         Ok(metadata) => {
                 // This can return an error result
-                let bytes_error = fs::read(file);
+                let bytes_error = fs::read(&file);
                 match bytes_error {
                     // Remove the error result so the extract_bytes function can read it
                     Ok(bytes) => {
-                        let header = find_header(mode, bytes.clone());
-                        let extracted_bytes = if header != "INVALID" {
-                            extract_bytes(&header, bytes.clone())
-                        } else {
-                            bytes.clone()
-                        };
+                        let header = find_header(mode, &bytes);
+                        let mut extracted_bytes = extract_bytes(header.as_ref(), bytes);
+
+                        // Convert the asset if the user asked for a different format
+                        let mut converted = None;
+                        if let Some(format) = convert_to {
+                            match convert::convert(&extracted_bytes, format) {
+                                Ok(converted_bytes) => {
+                                    extracted_bytes = converted_bytes;
+                                    converted = Some(format);
+                                }
+                                Err(e) => {
+                                    // Writing the original under the converted name would only give a broken file
+                                    let mut args = FluentArgs::new();
+                                    args.set("error", e.clone());
+                                    log::error(&format!("Failed to convert '{}' to {}: {}", file.display(), format, e));
+                                    update_status(locale::get_message(&locale::get_locale(None), "error-converting-file", Some(&args)));
+                                    return Err(e);
+                                }
+                            }
+                        }
 
                         // Add the extention if needed
                         if add_extention {
-                            let extentions = {EXTENTION.lock().unwrap().clone()};
-                            if let Some(extention) = extentions.get(&header) {
-                                destination.set_extension(&extention);
+                            if let Some(format) = converted {
+                                destination.set_extension(format);
+                            } else if let Some(signature) = &header {
+                                destination.set_extension(&signature.extension);
+                            } else if let Some(signature) = find_header("sounds", &extracted_bytes) {
+                                destination.set_extension(&signature.extension); // Music tab, it has no signatures of its own
                             } else {
-                                destination.set_extension(".ogg"); // Music tab
+                                destination.set_extension("ogg");
                             }
                         }
 
@@ -585,14 +880,14 @@ pub fn extract_file(file: PathBuf, mode: &str, destination: PathBuf, add_extenti
                             }
                         }                        
 
-                        return destination;
+                        return Ok(destination);
 
 
                     }
                     Err(e) => {
                         update_status(locale::get_message(&locale::get_locale(None), "failed-opening-file", None));
                         log::error(&format!("Failed to open file: {}", e));
-                        return PathBuf::new();
+                        return Err(e.to_string());
                     }
                 }
             // Error handling just so the program doesn't crash for seemingly no reason
@@ -604,7 +899,7 @@ pub fn extract_file(file: PathBuf, mode: &str, destination: PathBuf, add_extenti
 
             log::error(&format!("Error extracting file: '{}' {}", file.display(), e));
             update_status(locale::get_message(&locale::get_locale(None), "idling", Some(&args)));
-            return PathBuf::new();
+            return Err(e.to_string());
         }
     }
 }
@@ -615,12 +910,8 @@ pub fn extract_file_to_bytes(file: PathBuf, mode: &str) -> Vec<u8> {
     match bytes_error {
         // Remove the error result so the extract_bytes function can read it
         Ok(bytes) => {
-            let header = find_header(mode, bytes.clone());
-            let extracted_bytes = if header != "INVALID" {
-                extract_bytes(&header, bytes.clone())
-            } else {
-                bytes.clone()
-            };
+            let header = find_header(mode, &bytes);
+            let extracted_bytes = extract_bytes(header.as_ref(), bytes);
 
             return extracted_bytes;
 
@@ -634,7 +925,210 @@ pub fn extract_file_to_bytes(file: PathBuf, mode: &str) -> Vec<u8> {
 }
 
 
-pub fn extract_dir(dir: PathBuf, destination: PathBuf, mode: String, yield_for_thread: bool, use_alias: bool) {
+/// Decode an extracted image, KTX and KTX2 textures are decoded on the CPU.
+/// A preview size picks the smallest mip level that is at least that size.
+pub fn decode_image(bytes: &[u8], preview_size: Option<u32>) -> image::ImageResult<image::RgbaImage> {
+    if ktx::is_ktx(bytes) {
+        let texture = ktx::KtxTexture::parse(bytes).map_err(image::ImageError::IoError)?;
+        let level = preview_size.map(|size| texture.preview_level(size)).unwrap_or(0);
+        return texture.decode_level(level).map_err(image::ImageError::IoError)
+    }
+    if ktx2::is_ktx2(bytes) {
+        let texture = ktx2::Ktx2Texture::parse(bytes).map_err(image::ImageError::IoError)?;
+        let level = preview_size.map(|size| texture.preview_level(size)).unwrap_or(0);
+        return texture.decode_level(level).map_err(image::ImageError::IoError)
+    }
+    match image::guess_format(bytes) {
+        Ok(format) => Ok(image::load_from_memory_with_format(bytes, format)?.to_rgba8()),
+        // TGA files have no magic bytes to guess them from, so only their header is checked
+        Err(_) if is_plausible_tga(bytes) => Ok(image::load_from_memory_with_format(bytes, image::ImageFormat::Tga)?.to_rgba8()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Whether the bytes start with a TGA header that describes a real image
+fn is_plausible_tga(bytes: &[u8]) -> bool {
+    let Some(header) = bytes.get(..18) else {
+        return false
+    };
+    let width = u16::from_le_bytes([header[12], header[13]]);
+    let height = u16::from_le_bytes([header[14], header[15]]);
+    header[1] <= 1 // Colour map type
+        && matches!(header[2], 1 | 2 | 3 | 9 | 10 | 11) // Image type, 0 means there is no image
+        && matches!(header[16], 8 | 15 | 16 | 24 | 32) // Bits per pixel
+        && width > 0 && height > 0
+}
+
+/// Frames of an animated GIF or WebP, empty if the image isn't animated
+pub fn decode_animation(bytes: &[u8], preview_size: Option<u32>) -> image::ImageResult<Vec<animation::Frame>> {
+    animation::decode_frames(bytes, preview_size)
+}
+
+/// Draw a shaded preview of a mesh, as meshes have no image of their own
+pub fn render_mesh(bytes: &[u8], size: u32) -> image::ImageResult<image::RgbaImage> {
+    let mesh = mesh::Mesh::parse(bytes).map_err(image::ImageError::IoError)?;
+    Ok(render::render(&mesh, size))
+}
+
+pub fn get_conversions(mode: &str) -> Vec<&'static str> {
+    convert::get_conversions(mode)
+}
+
+/// Length and format of a sound from the cache, used by --info
+pub fn read_audio(file: PathBuf, mode: &str) -> Option<AudioInfo> {
+    audio::probe(&extract_file_to_bytes(file, mode))
+}
+
+/// Size and format of an image or texture from the cache, used by --info
+pub fn read_image(file: PathBuf, mode: &str) -> Option<ImageInfo> {
+    image_info::probe(&extract_file_to_bytes(file, mode))
+}
+
+/// Decode a sound from the cache for the built-in player
+pub fn decode_sound(file: PathBuf, mode: &str) -> Result<pcm::Pcm, String> {
+    pcm::Pcm::decode(&extract_file_to_bytes(file, mode))
+}
+
+/// Draw the waveform of a sound, as sounds have no image of their own
+pub fn render_waveform(bytes: &[u8], size: u32) -> image::ImageResult<image::RgbaImage> {
+    let pcm = pcm::Pcm::decode(bytes).map_err(|e| image::ImageError::IoError(std::io::Error::other(e)))?;
+    Ok(pcm.waveform_image(size, size))
+}
+
+/// Parse a binary model from the cache, used by the details pane and --info
+pub fn read_model(file: PathBuf) -> Result<rbxm::Model, String> {
+    let bytes = extract_file_to_bytes(file, "rbxm-files");
+    rbxm::Model::parse(&bytes).map_err(|e| e.to_string())
+}
+
+fn seconds_since_epoch(time: Option<SystemTime>) -> u64 {
+    time.and_then(|time| time.duration_since(SystemTime::UNIX_EPOCH).ok()).map_or(0, |duration| duration.as_secs())
+}
+
+/// Hash every listed image and find the ones that look like the given one, for the images tab.
+/// Hashes are kept in an index, so only new or changed images have to be decoded again.
+/// Returns false without searching if another task is still running
+pub fn find_similar_images(dir: PathBuf, name: String, yield_for_thread: bool) -> bool {
+    // Stop multiple threads from running, checked and set at once so two searches can't both start
+    {
+        let mut task = TASK_RUNNING.lock().unwrap();
+        if *task {
+            update_status(locale::get_message(&locale::get_locale(None), "task-already-running", None));
+            return false
+        }
+        *task = true;
+    }
+    let handle = thread::spawn(move || {
+        SIMILAR_FILE_LIST.lock().unwrap().clear();
+
+        let locale = locale::get_locale(None);
+        let index_path = config::get_data_file(IMAGE_HASH_INDEX_FILE);
+        let mut index = image_hash::HashIndex::load(&index_path);
+        let file_list: Vec<AssetInfo> = get_file_list().into_iter().filter(|asset| asset.real_asset).collect();
+
+        let total = file_list.len();
+        let mut hashes = Vec::with_capacity(total);
+        for (count, asset) in file_list.iter().enumerate() {
+            update_progress((count + 1) as f32 / total as f32);
+            let mut args = FluentArgs::new();
+            args.set("item", count + 1);
+            args.set("total", total);
+            update_status(locale::get_message(&locale, "hashing-images", Some(&args)));
+
+            let modified = seconds_since_epoch(asset.last_modified);
+            let hash = match index.get(&asset.name, asset.size, modified) {
+                Some(hash) => Some(hash),
+                None => {
+                    let bytes = extract_file_to_bytes(dir.join(&asset.name), "images");
+                    match decode_image(&bytes, None) {
+                        Ok(image) => {
+                            let hash = image_hash::ImageHash::new(&image);
+                            index.insert(&asset.name, asset.size, modified, hash);
+                            Some(hash)
+                        }
+                        Err(e) => {
+                            log::warn(&format!("Failed to hash {}: {}", asset.name, e));
+                            None
+                        }
+                    }
+                }
+            };
+            if let Some(hash) = hash {
+                hashes.push((asset, hash));
+            }
+        }
+
+        // Deleted images don't need to stay in the index
+        index.retain(|name| file_list.iter().any(|asset| asset.name == name));
+        if let Err(e) = index.save(&index_path) {
+            log::error(&format!("Failed to save the image hash index: {}", e));
+        }
+
+        let threshold = config::get_config_u64("similar_images_threshold").unwrap_or(8) as u32;
+        let similar = match hashes.iter().find(|(asset, _)| asset.name == name) {
+            Some((_, target)) => {
+                let mut similar: Vec<(AssetInfo, u32)> = hashes.iter()
+                    .map(|(asset, hash)| ((*asset).clone(), target.distance(hash)))
+                    .filter(|(_, distance)| *distance <= threshold)
+                    .collect();
+                similar.sort_by_key(|(_, distance)| *distance);
+                similar
+            }
+            None => Vec::new(), // The image itself couldn't be read
+        };
+
+        let mut args = FluentArgs::new();
+        args.set("count", similar.len().saturating_sub(1)); // Without the image itself
+        *SIMILAR_FILE_LIST.lock().unwrap() = similar;
+        {
+            let mut task = TASK_RUNNING.lock().unwrap();
+            *task = false; // Allow other threads to run again
+        }
+        update_status(locale::get_message(&locale, "similar-images-found", Some(&args)));
+    });
+
+    if yield_for_thread {
+        // Will wait for the thread instead of quitting immediately
+        let _ = handle.join();
+    }
+    true
+}
+
+/// Whether refreshing hashes the assets, either because it is turned on in the settings or for this session
+pub fn get_detect_duplicates() -> bool {
+    config::get_config_bool("detect_duplicates").unwrap_or(false) || *DETECT_DUPLICATES_THIS_SESSION.lock().unwrap()
+}
+
+/// Hash the assets when refreshing until the program closes, without changing the setting
+pub fn detect_duplicates_this_session() {
+    *DETECT_DUPLICATES_THIS_SESSION.lock().unwrap() = true;
+}
+
+/// Assets that are cached more than once with the amount of copies, each group of copies is kept together
+pub fn get_duplicate_file_list() -> Vec<(AssetInfo, usize)> {
+    let mut groups: Vec<Vec<AssetInfo>> = Vec::new();
+    let mut group_of_hash = HashMap::new();
+    for asset in get_file_list() {
+        let Some(hash) = asset.payload_hash else { continue };
+        let index = *group_of_hash.entry(hash).or_insert_with(|| {
+            groups.push(Vec::new());
+            groups.len() - 1
+        });
+        groups[index].push(asset);
+    }
+    groups.retain(|group| group.len() > 1);
+    groups.sort_by_key(|group| std::cmp::Reverse(group.len())); // Most copies first
+    groups.into_iter().flat_map(|group| {
+        let copies = group.len();
+        group.into_iter().map(move |asset| (asset, copies))
+    }).collect()
+}
+
+pub fn get_similar_file_list() -> Vec<(AssetInfo, u32)> {
+    SIMILAR_FILE_LIST.lock().unwrap().clone()
+}
+
+pub fn extract_dir(dir: PathBuf, destination: PathBuf, mode: String, yield_for_thread: bool, use_alias: bool, convert_to: Option<String>, unique_only: bool) {
     // Create directory if it doesn't exist
     match fs::create_dir_all(destination.clone()) {
         Ok(_) => (),
@@ -662,35 +1156,41 @@ pub fn extract_dir(dir: PathBuf, destination: PathBuf, mode: String, yield_for_t
             // Get locale for localised status messages
             let locale = locale::get_locale(None);
 
-            // Get amount and initlilize counter for progress
-            let total = file_list.len();
-            let mut count = 0;
+            // Only the first copy of each asset is kept when extracting unique assets
+            let file_list = if unique_only {
+                first_copies(file_list, &mut HashSet::new(), &locale, |entry| {
+                    entry.payload_hash.or_else(|| read_payload_hash(&dir.join(&entry.name), &mode))
+                })
+            } else {
+                file_list
+            };
 
-            for entry in file_list {
-                count += 1; // Increase counter for progress
-                update_progress(count as f32/total as f32); // Convert to f32 to allow floating point output
-                let origin =  dir.join(&entry.name);
+            // Get amount for progress
+            let total = file_list.len();
 
-                let alias = if use_alias {
-                    config::get_asset_alias(&entry.name)
-                } else {
-                    entry.name
-                };
+            // Decided before the workers start, as they would race for a shared alias
+            let destinations = destination_paths(file_list.iter().map(|entry| (destination.clone(), entry.name.as_str())), use_alias);
+            let files: Vec<_> = file_list.iter().zip(destinations).collect();
 
-                let dest = destination.join(alias); // Local variable destination
+            worker_pool::run(&files, worker_count(), |(entry, dest)| {
+                let origin = dir.join(&entry.name);
+                extract_file(origin, &mode, dest.clone(), true, convert_to.as_deref())
+            }, |position, result| {
+                let count = position + 1;
+                update_progress(count as f32/total as f32); // Convert to f32 to allow floating point output
 
                 // Args for formatting
                 let mut args = FluentArgs::new();
                 args.set("item", count);
                 args.set("total", total);
 
-                let result = extract_file(origin, &mode, dest, true);
-                if result == PathBuf::new() {
+                if result.is_err() {
                     update_status(locale::get_message(&locale, "failed-extracting-file", Some(&args)));
                 } else {
                     update_status(locale::get_message(&locale, "extracting-files", Some(&args)));
                 }
-            }
+                true
+            });
             { 
                 let mut task = TASK_RUNNING.lock().unwrap();
                 *task = false; // Allow other threads to run again
@@ -705,7 +1205,96 @@ pub fn extract_dir(dir: PathBuf, destination: PathBuf, mode: String, yield_for_t
     }
 }
 
-pub fn extract_all(destination: PathBuf, yield_for_thread: bool, use_alias: bool) {
+/// Write the source of every script in the cached models to .lua files, in folders named after the model
+pub fn extract_scripts(dir: PathBuf, destination: PathBuf, yield_for_thread: bool) {
+    let running = *TASK_RUNNING.lock().unwrap();
+    // Stop multiple threads from running
+    if !running {
+        let handle = thread::spawn(move || {
+            { 
+                let mut task = TASK_RUNNING.lock().unwrap();
+                *task = true; // Stop other threads from running
+            }
+
+            // User has configured it to refresh before extracting
+            if config::get_config_bool("refresh_before_extract").unwrap_or(false) {
+                refresh(dir.clone(), "rbxm-files".to_owned(), false, true);
+            }
+
+            // Entries such as "No files" aren't models
+            let file_list: Vec<AssetInfo> = get_file_list().into_iter().filter(|asset| asset.real_asset).collect();
+
+            // Get locale for localised status messages
+            let locale = locale::get_locale(None);
+
+            // Get amount and initlilize counter for progress
+            let total = file_list.len();
+            let mut count = 0;
+            let mut scripts = 0;
+
+            for entry in file_list {
+                count += 1; // Increase counter for progress
+                update_progress(count as f32/total as f32);
+
+                // Args for formatting
+                let mut args = FluentArgs::new();
+                args.set("item", count);
+                args.set("total", total);
+                update_status(locale::get_message(&locale, "extracting-scripts", Some(&args)));
+
+                let model = match read_model(dir.join(&entry.name)) {
+                    Ok(model) => model,
+                    Err(e) => {
+                        log::error(&format!("Failed to read model '{}': {}", entry.name, e));
+                        continue
+                    }
+                };
+
+                let model_destination = destination.join(config::get_asset_alias(&entry.name));
+                for (path, source) in model.script_files() {
+                    let path = model_destination.join(path);
+                    if let Some(parent) = path.parent() {
+                        if let Err(e) = fs::create_dir_all(parent) {
+                            log::error(&format!("Error creating directory: {}", e));
+                            continue
+                        }
+                    }
+                    match fs::write(&path, source) {
+                        Ok(_) => scripts += 1,
+                        Err(e) => log::error(&format!("Failed to write '{}': {}", path.display(), e)),
+                    }
+                }
+            }
+            { 
+                let mut task = TASK_RUNNING.lock().unwrap();
+                *task = false; // Allow other threads to run again
+            }
+            let mut args = FluentArgs::new();
+            args.set("count", scripts);
+            update_status(locale::get_message(&locale, "all-scripts-extracted", Some(&args)));
+        });
+        
+        if yield_for_thread {
+            // Will wait for the thread instead of quitting immediately
+            let _ = handle.join();
+        }
+    }
+}
+
+pub fn extract_all(destination: PathBuf, yield_for_thread: bool, use_alias: bool, unique_only: bool) {
+    extract_cache(destination, yield_for_thread, use_alias, unique_only, None)
+}
+
+/// Extract the assets that were added or changed during the last capture, sorted into a folder for each category
+pub fn extract_captured(destination: PathBuf, yield_for_thread: bool, use_alias: bool, unique_only: bool) {
+    let captured = CAPTURED.lock().unwrap().clone();
+    if captured.is_some() {
+        extract_cache(destination, yield_for_thread, use_alias, unique_only, captured)
+    }
+}
+
+/// Extract every asset in the cache, or only the ones that changed since the snapshot was taken
+fn extract_cache(destination: PathBuf, yield_for_thread: bool, use_alias: bool, unique_only: bool, since: Option<snapshot::Snapshot>) {
     let running = {
         let task = TASK_RUNNING.lock().unwrap();
         task.clone()
@@ -721,16 +1310,8 @@ pub fn extract_all(destination: PathBuf, yield_for_thread: bool, use_alias: bool
             // Get locale for localised status messages
             let locale = locale::get_locale(None);
 
-            let headers = {HEADERS.lock().unwrap().clone()};
-            let mut all_headers: Vec<(String, String)> = Vec::new();
-
-            for key in headers.keys() {
-                if let Some(mode_headers) = headers.get(key) {
-                    for single_header in mode_headers {
-                        all_headers.push((single_header.to_string(), key.to_string()));
-                    }
-                }
-            }
+            let all_signatures = signatures::get_signatures();
+            let categories = signatures::get_categories();
 
             let cache_directory = get_cache_directory();
             let music_directory = cache_directory.join("sounds");
@@ -742,18 +1323,37 @@ pub fn extract_all(destination: PathBuf, yield_for_thread: bool, use_alias: bool
             let _ = fs::create_dir_all(music_destination);
 
             // Loop through all types and create directories for them
-            for key in headers.keys() {
-                let _ = fs::create_dir_all(destination.join(key));
+            for category in &categories {
+                let _ = fs::create_dir_all(destination.join(category));
             }
 
+            let mut extracted = HashSet::new(); // Hashes of the assets that were extracted, for unique_only
+
             // Stage 1: Read and extract music directory
-            let entries: Vec<_> = fs::read_dir(music_directory.clone()).unwrap().collect();
+            let paths: Vec<PathBuf> = fs::read_dir(music_directory.clone()).unwrap()
+                .map(|entry| entry.unwrap().path())
+                .filter(|path| since.as_ref().is_none_or(|since| is_changed_since(since, "sounds", path)))
+                .collect();
+            let paths = if unique_only {
+                first_copies(paths, &mut extracted, &locale, |path| read_payload_hash(path, "music"))
+            } else {
+                paths
+            };
 
-            // Get amount and initlilize counter for progress
-            let total = entries.len();
-            let mut count = 0;
-            for entry in entries {                            
-                count += 1; // Increase counter for progress
+            // Get amount for progress
+            let total = paths.len();
+
+            // Decided before the workers start, as they would race for a shared alias
+            let names: Vec<String> = paths.iter()
+                .map(|path| path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default())
+                .collect();
+            let destinations = destination_paths(names.iter().map(|name| (destination.clone(), name.as_str())), use_alias);
+            let files: Vec<_> = paths.iter().zip(destinations).collect();
+
+            worker_pool::run(&files, worker_count(), |(path, dest)| {
+                let _ = extract_file(path.to_path_buf(), "Music", dest.clone(), true, None);
+            }, |position, _| {
+                let count = position + 1;
                 update_progress((count as f32/total as f32) / 3.0);
 
                 // Args for formatting
@@ -766,34 +1366,40 @@ pub fn extract_all(destination: PathBuf, yield_for_thread: bool, use_alias: bool
                 args.set("max", "3");
                 args.set("status", locale::get_message(&locale, "extracting-files", Some(&args)));
                 update_status(locale::get_message(&locale, "stage", Some(&args)));
-
-                let path = entry.unwrap().path();
-                if let Some(filename) = path.file_name() {
-                    let name = filename.to_string_lossy().to_string();
-
-                    let alias = if use_alias {
-                        config::get_asset_alias(&name)
-                    } else {
-                        name
-                    };
-
-
-                    let dest = destination.join(alias); // Local destination
-                    extract_file(path, "Music", dest, true);
-                }
-            }
+                true
+            });
 
             // Stage 2: Filter the files
-            let entries: Vec<_> = fs::read_dir(&http_directory).unwrap().collect();
+            let entries: Vec<_> = fs::read_dir(&http_directory).unwrap()
+                .filter(|entry| match (entry, &since) {
+                    (Ok(entry), Some(since)) => is_changed_since(since, "http", &entry.path()),
+                    _ => true,
+                })
+                .collect();
 
             // Initilize the Vec for the filtered files to go in
             let mut filtered_files: Vec<(String, String)> = Vec::new();
 
-            // Get amount and initlilize counter for progress
+            // Get amount for progress
             let total = entries.len();
-            let mut count = 0;
-            for entry in entries {                            
-                count += 1; // Increase counter for progress
+            worker_pool::run(&entries, worker_count(), |entry| -> std::io::Result<Option<(String, String)>> {
+                let path = match entry {
+                    Ok(entry) => entry.path(),
+                    Err(e) => return Err(std::io::Error::new(e.kind(), e.to_string())),
+                };
+
+                // Safely unwrap the file name
+                let filename = path.file_name().ok_or_else(|| std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    "No filename!"
+                ))?;
+
+                let buffer = read_head(&path)?;
+
+                // Add it to the list with the category of the first signature that matches the file.
+                Ok(identify(&all_signatures, &buffer).map(|signature| (filename.to_string_lossy().to_string(), signature.category)))
+            }, |position, result| {
+                let count = position + 1;
                 update_progress(((count as f32/total as f32) + 1.0) / 3.0); // 2nd stage, will fill up the bar from 1/3 to 2/3
 
                 // Args for formatting
@@ -803,40 +1409,9 @@ pub fn extract_all(destination: PathBuf, yield_for_thread: bool, use_alias: bool
                 args.set("stage", "2");
                 args.set("max", "3");
 
-                let result = {
-                    let all_headers = &all_headers;
-                    let filtered_files = &mut filtered_files;
-                    move || -> std::io::Result<()> {
-                    let path = entry?.path();
-
-                    // Safely unwrap the file name
-                    let filename = path.file_name().ok_or_else(|| std::io::Error::new(
-                        std::io::ErrorKind::Other,
-                        "No filename!"
-                    ))?;
-
-                    let mut file = fs::File::open(&path)?;
-                    
-                    // Reading the first 2048 bytes of the file
-                    let mut buffer = vec![0; 2048];
-                    let bytes_read = file.read(&mut buffer)?;
-                    buffer.truncate(bytes_read);
-
-                    // header.0 = header, header.1 = mode
-                    for header in all_headers {
-                        // Check if header is not empty before actually checking file
-                        if header.0 != "" {
-                            // Add it to the list if the header is inside of the file.
-                            if bytes_contains(&buffer, header.0.as_bytes()) {                                        
-                                filtered_files.push((filename.to_string_lossy().to_string(), header.1.to_string()))
-                            }
-                        }
-                    }
-
-                    Ok(())
-                }}();
                 match result {
-                    Ok(()) => {
+                    Ok(file) => {
+                        filtered_files.extend(file);
                         args.set("status", locale::get_message(&locale, "filtering-files", Some(&args)));
                         update_status(locale::get_message(&locale, "stage", Some(&args)));
                     }
@@ -846,15 +1421,27 @@ pub fn extract_all(destination: PathBuf, yield_for_thread: bool, use_alias: bool
                         update_status(locale::get_message(&locale, "stage", Some(&args)));
                     }
                 }
-            }
+                true
+            });
 
             // Stage 3: Extract the files
+            let filtered_files = if unique_only {
+                first_copies(filtered_files, &mut extracted, &locale, |file| read_payload_hash(&http_directory.join(&file.0), &file.1))
+            } else {
+                filtered_files
+            };
 
-            // Get amount and initlilize counter for progress
+            // Get amount for progress
             let total = filtered_files.len();
-            let mut count = 0;
-            for file in filtered_files {
-                count += 1; // Increase counter for progress
+            // Stores in (destination/type/name), decided before the workers start as they would race for a shared alias
+            let destinations = destination_paths(filtered_files.iter().map(|file| (destination.join(&file.1), file.0.as_str())), use_alias);
+            let files: Vec<_> = filtered_files.iter().zip(destinations).collect();
+
+            worker_pool::run(&files, worker_count(), |(file, dest)| {
+                let origin = http_directory.join(&file.0);
+                let _ = extract_file(origin, &file.1, dest.clone(), true, None);
+            }, |position, _| {
+                let count = position + 1;
                 update_progress(((count as f32/total as f32) + 2.0) / 3.0); // 3rd stage, will fill up the bar from 2/3 to 3/3
 
                 // Args for formatting
@@ -862,24 +1449,14 @@ pub fn extract_all(destination: PathBuf, yield_for_thread: bool, use_alias: bool
                 args.set("item", count);
                 args.set("total", total);
 
-                let origin = http_directory.join(&file.0);
-                
-                let alias = if use_alias {
-                    config::get_asset_alias(&file.0)
-                } else {
-                    file.0
-                };
-
-                let dest = destination.join(&file.1).join(alias); // Local destination, stores in (destination/type/name)
-                extract_file(origin, &file.1, dest, true);
-
                 // More formatting to show "Stage 3/3: Extracting files"
                 args.set("status", locale::get_message(&locale, "extracting-files", Some(&args)));
                 args.set("stage", "3");
                 args.set("max", "3");
 
                 update_status(locale::get_message(&locale, "stage", Some(&args)));
-            }
+                true
+            });
 
             { 
                 let mut task = TASK_RUNNING.lock().unwrap();
@@ -954,8 +1531,25 @@ pub fn copy_assets(dir: PathBuf, asset_a: &str, asset_b: &str) {
     }
 }
 
+impl AssetInfo {
+    /// Words describing the asset that can be searched for, such as "1024x1024", "alpha" or "vorbis"
+    fn search_tags(&self) -> String {
+        let mut tags = Vec::new();
+        if let Some(image) = &self.image {
+            tags.push(format!("{}x{}", image.width, image.height));
+            tags.push(image.format.to_lowercase());
+            tags.push(if image.alpha { "alpha" } else { "opaque" }.to_owned());
+        }
+        if let Some(audio) = &self.audio {
+            tags.push(audio.codec.to_lowercase());
+        }
+        tags.join(" ")
+    }
+}
+
 pub fn filter_file_list(query: String) {
     let query_lower = query.to_lowercase();
+    *FILTER_QUERY.lock().unwrap() = query;
     // Clear file list before
     {
         let mut filtered_file_list = FILTERED_FILE_LIST.lock().unwrap();
@@ -963,7 +1557,13 @@ pub fn filter_file_list(query: String) {
     }
     let file_list = get_file_list(); // Clone file list
     for file in file_list {
-        if file.name.contains(&query_lower) || config::get_asset_alias(&file.name).to_lowercase().contains(&query_lower) {
+        // Every word has to match, so "1024x1024 alpha" finds large transparent images
+        let alias = config::get_asset_alias(&file.name).to_lowercase();
+        let tags = file.search_tags();
+        let matches = query_lower.split_whitespace().all(|term| {
+            file.name.contains(term) || alias.contains(term) || tags.split(' ').any(|tag| tag.starts_with(term))
+        });
+        if matches {
             {
                 let mut filtered_file_list = FILTERED_FILE_LIST.lock().unwrap();
                 filtered_file_list.push(file);
@@ -972,6 +1572,29 @@ pub fn filter_file_list(query: String) {
     }
 }
 
+/// Sort the listed assets, "default" puts them back in the order they were read in
+pub fn sort_file_list(key: &str) {
+    let compare = |a: &AssetInfo, b: &AssetInfo| -> std::cmp::Ordering {
+        match key {
+            "name" => config::get_asset_alias(&a.name).cmp(&config::get_asset_alias(&b.name)),
+            // Largest, newest and longest first
+            "size" => b.size.cmp(&a.size),
+            "modified" => b.last_modified.cmp(&a.last_modified),
+            "dimensions" => {
+                let pixels = |asset: &AssetInfo| asset.image.as_ref().map(|image| image.width as u64 * image.height as u64);
+                pixels(b).cmp(&pixels(a))
+            }
+            "duration" => {
+                let duration = |asset: &AssetInfo| asset.audio.as_ref().and_then(|audio| audio.duration);
+                duration(b).cmp(&duration(a))
+            }
+            _ => a.read_order.cmp(&b.read_order),
+        }
+    };
+    FILE_LIST.lock().unwrap().sort_by(compare);
+    FILTERED_FILE_LIST.lock().unwrap().sort_by(compare);
+}
+
 pub fn get_file_list() -> Vec<AssetInfo> {
     FILE_LIST.lock().unwrap().clone()
 }
@@ -995,8 +1618,277 @@ pub fn get_mode_cache_directory(mode: &str) -> PathBuf {
 }
 
 pub fn set_cache_directory(value: PathBuf) {
-    let mut cache_directory = CACHE_DIRECTORY.lock().unwrap();
-    *cache_directory = value;
+    {
+        let mut cache_directory = CACHE_DIRECTORY.lock().unwrap();
+        *cache_directory = value;
+    }
+    // Watch the new directory instead
+    let watching = CACHE_WATCHER.lock().unwrap().is_some();
+    if watching {
+        watch_cache(true);
+    }
+}
+
+/// Remember what is in the cache, so the assets a game downloads from now on can be found when the capture stops
+pub fn start_capture() {
+    let snapshot = snapshot::Snapshot::take(&get_cache_directory());
+    if let Err(e) = snapshot.save(&config::get_data_file(CAPTURE_FILE)) {
+        log::error(&format!("Failed to save the capture: {}", e));
+    }
+    *CAPTURE.lock().unwrap() = Some(snapshot);
+    update_status(locale::get_message(&locale::get_locale(None), "capture-started", None));
+}
+
+/// Stop the running capture, the assets that were added or changed since it started become the captured assets.
+/// Returns false if no capture was running.
+pub fn stop_capture() -> bool {
+    let snapshot = CAPTURE.lock().unwrap().take();
+    if snapshot.is_none() {
+        return false
+    }
+    if let Err(e) = fs::remove_file(config::get_data_file(CAPTURE_FILE)) {
+        log::warn(&format!("Failed to remove the capture: {}", e));
+    }
+    *CAPTURED.lock().unwrap() = snapshot;
+    update_status(locale::get_message(&locale::get_locale(None), "capture-stopped", None));
+    true
+}
+
+/// When the running capture started, None if there is no capture running
+pub fn get_capture_start() -> Option<SystemTime> {
+    CAPTURE.lock().unwrap().as_ref().map(|snapshot| snapshot.taken)
+}
+
+/// Whether a file was added or changed after the snapshot was taken
+fn is_changed_since(snapshot: &snapshot::Snapshot, directory: &str, path: &PathBuf) -> bool {
+    let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+    match fs::metadata(path) {
+        Ok(metadata) => snapshot.is_changed(directory, &name, metadata.len(), metadata.modified().ok()),
+        Err(_) => false, // Deleted since it was listed
+    }
+}
+
+/// Listed assets that were added or changed during the last capture, None if nothing was captured yet
+pub fn get_captured_file_list(mode: &str) -> Option<Vec<AssetInfo>> {
+    let captured = CAPTURED.lock().unwrap();
+    let snapshot = captured.as_ref()?;
+    let directory = if mode == "music" { "sounds" } else { "http" };
+    Some(get_file_list().into_iter()
+        .filter(|asset| asset.real_asset && snapshot.is_changed(directory, &asset.name, asset.size, asset.last_modified))
+        .collect())
+}
+
+fn snapshot_path(name: &str) -> PathBuf {
+    config::get_data_file(SNAPSHOT_DIRECTORY).join(format!("{}.json", name))
+}
+
+/// Names are used as file names, so they can't contain path separators or characters Windows doesn't allow
+fn validate_snapshot_name(name: &str) -> Result<(), String> {
+    if name.trim().is_empty() || name.starts_with('.') || name.contains(['/', '\\', ':', '*', '?', '"', '<', '>', '|']) {
+        Err(format!("'{}' can't be used as a snapshot name", name))
+    } else {
+        Ok(())
+    }
+}
+
+/// Save the size, modification time, category and hash of every file in the cache under a name, to compare it with another snapshot later
+pub fn save_snapshot(name: String, yield_for_thread: bool) {
+    if let Err(e) = validate_snapshot_name(&name) {
+        log::error(&e);
+        update_status(locale::get_message(&locale::get_locale(None), "error-check-logs", None));
+        return
+    }
+    // Stop multiple threads from running
+    if *TASK_RUNNING.lock().unwrap() {
+        return
+    }
+    let handle = thread::spawn(move || {
+        {
+            let mut task = TASK_RUNNING.lock().unwrap();
+            *task = true; // Stop other threads from running
+        }
+
+        let locale = locale::get_locale(None);
+        let cache_directory = get_cache_directory();
+        let all_signatures = signatures::get_signatures();
+        let mut snapshot = snapshot::Snapshot::take(&cache_directory);
+
+        // Every file is read to find its category and hash its contents
+        let files = snapshot.files();
+        let total = files.len();
+        let mut assets = Vec::with_capacity(total);
+        worker_pool::run(&files, worker_count(), |(directory, name)| {
+            let path = cache_directory.join(directory).join(name);
+            let category = if directory == "sounds" {
+                Some("music".to_owned()) // Music has no signatures, everything in its directory is listed
+            } else {
+                read_head(&path).ok().and_then(|buffer| identify(&all_signatures, &buffer)).map(|signature| signature.category)
+            };
+            let hash = category.as_ref().and_then(|category| read_payload_hash(&path, category));
+            (category, hash)
+        }, |position, asset| {
+            let count = position + 1;
+            update_progress(count as f32/total as f32);
+
+            let mut args = FluentArgs::new();
+            args.set("item", count);
+            args.set("total", total);
+            update_status(locale::get_message(&locale, "reading-snapshot", Some(&args)));
+
+            assets.push(asset);
+            true
+        });
+        for ((directory, name), (category, hash)) in files.iter().zip(assets) {
+            snapshot.set_asset(directory, name, category, hash);
+        }
+
+        let path = snapshot_path(&name);
+        let result = fs::create_dir_all(config::get_data_file(SNAPSHOT_DIRECTORY)).and_then(|_| snapshot.save(&path));
+        {
+            let mut task = TASK_RUNNING.lock().unwrap();
+            *task = false; // Allow other threads to run again
+        }
+        match result {
+            Ok(()) => {
+                let mut args = FluentArgs::new();
+                args.set("name", name);
+                update_status(locale::get_message(&locale, "snapshot-saved", Some(&args)));
+            }
+            Err(e) => {
+                log::error(&format!("Failed to save the snapshot: {}", e));
+                update_status(locale::get_message(&locale, "error-check-logs", None));
+            }
+        }
+    });
+
+    if yield_for_thread {
+        // Will wait for the thread instead of quitting immediately
+        let _ = handle.join();
+    }
+}
+
+/// Names of the saved snapshots, in alphabetical order
+pub fn get_snapshot_names() -> Vec<String> {
+    let mut names: Vec<String> = match fs::read_dir(config::get_data_file(SNAPSHOT_DIRECTORY)) {
+        Ok(entries) => entries.flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+            .filter_map(|path| path.file_stem().map(|name| name.to_string_lossy().to_string()))
+            .collect(),
+        Err(_) => Vec::new(), // No snapshots were saved yet
+    };
+    names.sort();
+    names
+}
+
+pub fn delete_snapshot(name: &str) {
+    if validate_snapshot_name(name).is_ok() {
+        if let Err(e) = fs::remove_file(snapshot_path(name)) {
+            log::error(&format!("Failed to delete the snapshot '{}': {}", name, e));
+        }
+    }
+}
+
+/// Assets that were added, removed or modified between two saved snapshots, by category
+pub fn diff_snapshots(old: &str, new: &str) -> Result<BTreeMap<String, CategoryDiff>, String> {
+    let load = |name: &str| {
+        validate_snapshot_name(name)?;
+        snapshot::Snapshot::load(&snapshot_path(name)).map_err(|e| format!("Failed to read the snapshot '{}': {}", name, e))
+    };
+    Ok(load(old)?.diff(&load(new)?))
+}
+
+/// Start or stop watching the cache for assets that are added, changed or deleted while Roblox is running
+pub fn watch_cache(enabled: bool) {
+    let mut cache_watcher = CACHE_WATCHER.lock().unwrap();
+    *cache_watcher = None; // Stop watching the old directories first
+    if enabled {
+        let cache_directory = get_cache_directory();
+        let directories: Vec<PathBuf> = [cache_directory.join("http"), cache_directory.join("sounds")]
+            .into_iter()
+            .filter(|directory| directory.is_dir())
+            .collect();
+        match watcher::CacheWatcher::new(&directories, update_watched_files) {
+            Ok(watcher) => *cache_watcher = Some(watcher),
+            Err(e) => log::error(&format!("Failed to watch the cache: {}", e)),
+        }
+    }
+}
+
+/// Add, replace or remove the files the watcher saw change in the listed tab
+fn update_watched_files(paths: Vec<PathBuf>) {
+    // Refreshing clears the list, so wait for it to finish and stop it from starting until this is done
+    let _task = ListTask::acquire(false);
+
+    if let Some((mode, dir)) = LISTED_TAB.lock().unwrap().clone() {
+        let signatures = signatures::get_category_signatures(&mode);
+        let detect_duplicates = get_detect_duplicates();
+        let mut index = SCAN_INDEX.lock().unwrap();
+        let mut changed = false;
+
+        for path in paths.iter().filter(|path| path.parent() == Some(dir.as_path())) {
+            let name = match path.file_name() {
+                Some(name) => name.to_string_lossy().to_string(),
+                None => continue,
+            };
+
+            // Deleted files and files that don't belong in the tab are taken off the list
+            let asset = if path.is_file() && (mode == "music" || !signatures.is_empty()) {
+                match scan_file(path, &mode, &signatures, detect_duplicates, &index) {
+                    Ok(scanned) => {
+                        if let Some((size, modified)) = scanned.index_key {
+                            index.insert(&mode, &scanned.name, size, modified, scanned.asset.clone());
+                        }
+                        scanned.asset
+                    }
+                    Err(e) => {
+                        log::warn(&format!("Couldn't open file: {}", e));
+                        None
+                    }
+                }
+            } else {
+                None
+            };
+
+            let mut file_list = FILE_LIST.lock().unwrap();
+            let mut new_assets = NEW_ASSETS.lock().unwrap();
+            match (file_list.iter().position(|listed| listed.real_asset && listed.name == name), asset) {
+                (Some(position), Some(mut asset)) => {
+                    asset.read_order = file_list[position].read_order;
+                    file_list[position] = asset;
+                }
+                (Some(position), None) => {
+                    file_list.remove(position);
+                    new_assets.remove(&name);
+                }
+                (None, Some(mut asset)) => {
+                    file_list.retain(|listed| listed.real_asset); // "No files" isn't true anymore
+                    asset.read_order = file_list.iter().map(|listed| listed.read_order + 1).max().unwrap_or(0);
+                    file_list.push(asset);
+                    new_assets.insert(name);
+                }
+                (None, None) => continue,
+            }
+            changed = true;
+        }
+        drop(index);
+
+        if changed {
+            sort_file_list(&config::get_config_string("sort_by").unwrap_or("default".to_owned()));
+            let query = FILTER_QUERY.lock().unwrap().clone();
+            filter_file_list(query);
+            *REQUEST_REPAINT.lock().unwrap() = true;
+        }
+    }
+}
+
+/// Assets the watcher found since the tab was opened, for the "new" badge
+pub fn get_new_assets() -> HashSet<String> {
+    NEW_ASSETS.lock().unwrap().clone()
+}
+
+pub fn clear_new_assets() {
+    NEW_ASSETS.lock().unwrap().clear();
 }
 
 pub fn get_status() -> String {
@@ -1019,11 +1911,7 @@ pub fn get_request_repaint() -> bool {
 }
 
 pub fn get_categories() -> Vec<String> {
-    let mut catagories = Vec::new();
-    for key in HEADERS.lock().unwrap().keys() {
-        catagories.push(key.to_owned());
-    }
-    return catagories;
+    signatures::get_categories()
 }
 
 // Delete the temp directory
@@ -0,0 +1,80 @@
+// Decoder for the frames of animated GIF and WebP images
+//
+// The image crate composites every frame onto the full canvas, so each frame can be shown
+// on its own. Frames are scaled down to the preview size when one is given, as an
+// animation can hold hundreds of them.
+use std::io::Cursor;
+use std::time::Duration;
+
+use image::{imageops, AnimationDecoder, ImageResult, RgbaImage};
+use image::codecs::gif::GifDecoder;
+use image::codecs::webp::WebPDecoder;
+
+/// Frames past this are dropped, so a long animation can't use up all the memory
+const MAX_FRAMES: usize = 500;
+/// Frames are also dropped once they add up to this many pixels, 256 MiB as RGBA
+const MAX_PIXELS: u64 = 64 * 1024 * 1024;
+/// Browsers show frames with a delay this short for 100 ms instead, and files rely on it
+const MIN_DELAY: Duration = Duration::from_millis(20);
+const DEFAULT_DELAY: Duration = Duration::from_millis(100);
+
+pub struct Frame {
+    pub image: RgbaImage,
+    pub delay: Duration,
+}
+
+pub fn is_gif(bytes: &[u8]) -> bool {
+    bytes.starts_with(b"GIF8")
+}
+
+pub fn is_webp(bytes: &[u8]) -> bool {
+    bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(b"WEBP")
+}
+
+fn collect<'a>(decoder: impl AnimationDecoder<'a>, preview_size: Option<u32>) -> ImageResult<Vec<Frame>> {
+    let mut frames = Vec::new();
+    let mut pixels = 0;
+    for frame in decoder.into_frames().take(MAX_FRAMES) {
+        let frame = frame?;
+        let (numerator, denominator) = frame.delay().numer_denom_ms();
+        let delay = Duration::from_secs_f64(numerator as f64 / denominator.max(1) as f64 / 1000.0);
+        let image = frame.into_buffer();
+        let image = match preview_size {
+            Some(size) if image.width().max(image.height()) > size => {
+                // Keep the aspect ratio, the longest side becomes the preview size
+                let scale = size as f32 / image.width().max(image.height()) as f32;
+                let width = ((image.width() as f32 * scale).round() as u32).max(1);
+                let height = ((image.height() as f32 * scale).round() as u32).max(1);
+                imageops::resize(&image, width, height, imageops::FilterType::Triangle)
+            }
+            _ => image,
+        };
+        pixels += image.width() as u64 * image.height() as u64;
+        if pixels > MAX_PIXELS && !frames.is_empty() {
+            break
+        }
+        frames.push(Frame { image, delay: if delay < MIN_DELAY { DEFAULT_DELAY } else { delay } });
+    }
+    Ok(frames)
+}
+
+/// Decode every frame of an animated image, returns an empty list for still images and other formats
+pub fn decode_frames(bytes: &[u8], preview_size: Option<u32>) -> ImageResult<Vec<Frame>> {
+    let frames = if is_gif(bytes) {
+        collect(GifDecoder::new(Cursor::new(bytes))?, preview_size)?
+    } else if is_webp(bytes) {
+        let decoder = WebPDecoder::new(Cursor::new(bytes))?;
+        if !decoder.has_animation() {
+            return Ok(Vec::new())
+        }
+        collect(decoder, preview_size)?
+    } else {
+        return Ok(Vec::new())
+    };
+
+    // A GIF with one frame is just a still image
+    if frames.len() < 2 {
+        return Ok(Vec::new())
+    }
+    Ok(frames)
+}
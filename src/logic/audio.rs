@@ -0,0 +1,265 @@
+// Reads the length and format of sounds from their headers, without decoding them
+//
+// Ogg:
+//   Pages start with "OggS", the granule position (u64 at byte 6) counts the samples
+//   decoded by the end of the page, so the last page gives the length. The first packet
+//   is the codec's identification header, "\x01vorbis" or "OpusHead".
+// MP3:
+//   An optional ID3v2 tag, then frames that each start with a 4 byte header. The first
+//   frame of a VBR file holds a Xing/Info or VBRI header with the amount of frames,
+//   otherwise every frame is counted.
+//
+// Long sounds are probed from their first and last EXCERPT_LEN bytes, which hold every
+// header and the last Ogg page. Only MP3 files without a frame count need all the frames.
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+// Ogg page header before the segment table
+const OGG_PAGE_HEADER_LEN: usize = 27;
+// Opus always decodes at 48 kHz, whatever the input sample rate was
+const OPUS_RATE: u32 = 48000;
+// How far into an MP3 file the first frame is looked for, after the ID3 tag
+const MAX_SYNC_SEARCH: usize = 65536;
+/// Bytes needed from each end of a sound, Ogg pages are never longer than this
+pub const EXCERPT_LEN: usize = 65536;
+
+// Kilobits per second for MPEG-1 and MPEG-2/2.5, by layer
+const BITRATES_V1: [[u32; 15]; 3] = [
+    [0, 32, 64, 96, 128, 160, 192, 224, 256, 288, 320, 352, 384, 416, 448],
+    [0, 32, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320, 384],
+    [0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320],
+];
+const BITRATES_V2: [[u32; 15]; 3] = [
+    [0, 32, 48, 56, 64, 80, 96, 112, 128, 144, 160, 176, 192, 224, 256],
+    [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160],
+    [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160],
+];
+const SAMPLE_RATES: [u32; 3] = [44100, 48000, 32000];
+
+/// The start and end of a sound weren't enough to find its length
+#[derive(Debug)]
+pub struct NeedsWholeFile;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AudioInfo {
+    /// "Vorbis", "Opus", "MP3" and so on
+    pub codec: String,
+    pub duration: Option<Duration>,
+    pub sample_rate: u32,
+    pub channels: u8,
+    /// Bits per second, the average for variable bitrate files
+    pub bitrate: Option<u32>,
+}
+
+fn u16_le(bytes: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(bytes.get(offset..offset + 2)?.try_into().ok()?))
+}
+
+fn u32_le(bytes: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(bytes.get(offset..offset + 4)?.try_into().ok()?))
+}
+
+fn u32_be(bytes: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(bytes.get(offset..offset + 4)?.try_into().ok()?))
+}
+
+/// Average bitrate from the size of the audio data and its length
+fn average_bitrate(len: usize, duration: Option<Duration>) -> Option<u32> {
+    let seconds = duration?.as_secs_f64();
+    if seconds > 0.0 { Some((len as f64 * 8.0 / seconds) as u32) } else { None }
+}
+
+/// Granule position of the last page of the stream, pages are found from the end
+fn last_granule(bytes: &[u8], serial: u32) -> Option<u64> {
+    let mut end = bytes.len();
+    while let Some(start) = bytes[..end].windows(4).rposition(|window| window == b"OggS") {
+        let page = &bytes[start..];
+        if page.len() >= OGG_PAGE_HEADER_LEN && u32_le(page, 14) == Some(serial) {
+            let granule = u64::from_le_bytes(page[6..14].try_into().ok()?);
+            if granule != u64::MAX {
+                return Some(granule) // Pages that no packet ends on have no position
+            }
+        }
+        end = start;
+    }
+    None
+}
+
+fn probe_ogg(head: &[u8], tail: &[u8], len: usize) -> Option<AudioInfo> {
+    if !head.starts_with(b"OggS") || head.len() < OGG_PAGE_HEADER_LEN {
+        return None
+    }
+    let serial = u32_le(head, 14)?;
+    let segments = head[26] as usize;
+    let packet = head.get(OGG_PAGE_HEADER_LEN + segments..)?;
+    let granule = last_granule(tail, serial);
+
+    if packet.starts_with(b"\x01vorbis") {
+        let channels = *packet.get(11)?;
+        let sample_rate = u32_le(packet, 12)?;
+        let nominal = u32_le(packet, 20)? as i32;
+        let duration = match granule {
+            // A broken granule position can be longer than a Duration can hold
+            Some(samples) if sample_rate > 0 => Duration::try_from_secs_f64(samples as f64 / sample_rate as f64).ok(),
+            _ => None,
+        };
+        let bitrate = if nominal > 0 { Some(nominal as u32) } else { average_bitrate(len, duration) };
+        Some(AudioInfo { codec: "Vorbis".to_owned(), duration, sample_rate, channels, bitrate })
+    } else if packet.starts_with(b"OpusHead") {
+        let channels = *packet.get(9)?;
+        let pre_skip = u16_le(packet, 10)? as u64;
+        let input_rate = u32_le(packet, 12)?;
+        let duration = granule.and_then(|samples| Duration::try_from_secs_f64(samples.saturating_sub(pre_skip) as f64 / OPUS_RATE as f64).ok());
+        let sample_rate = if input_rate > 0 { input_rate } else { OPUS_RATE };
+        Some(AudioInfo { codec: "Opus".to_owned(), duration, sample_rate, channels, bitrate: average_bitrate(len, duration) })
+    } else {
+        None
+    }
+}
+
+struct FrameHeader {
+    /// 1 for MPEG-1, 2 for MPEG-2 and MPEG-2.5
+    version: u8,
+    layer: u8,
+    bitrate: u32,
+    sample_rate: u32,
+    channels: u8,
+    len: usize,
+    samples: u32,
+}
+
+fn parse_frame_header(header: u32) -> Option<FrameHeader> {
+    if header >> 21 != 0x7FF {
+        return None // No frame sync
+    }
+    let version_bits = (header >> 19) & 3;
+    let layer = match (header >> 17) & 3 {
+        3 => 1,
+        2 => 2,
+        1 => 3,
+        _ => return None,
+    };
+    let bitrate_index = ((header >> 12) & 0xF) as usize;
+    let rate_index = ((header >> 10) & 3) as usize;
+    if version_bits == 1 || bitrate_index == 0 || bitrate_index == 15 || rate_index == 3 {
+        return None // Reserved values, or free format which doesn't say how long frames are
+    }
+    let padding = (header >> 9) & 1;
+    let channels = if (header >> 6) & 3 == 3 { 1 } else { 2 };
+
+    let version = if version_bits == 3 { 1 } else { 2 };
+    let bitrate = if version == 1 { BITRATES_V1 } else { BITRATES_V2 }[layer as usize - 1][bitrate_index] * 1000;
+    let sample_rate = SAMPLE_RATES[rate_index] >> match version_bits { 3 => 0, 2 => 1, _ => 2 };
+    let samples = match (layer, version) {
+        (1, _) => 384,
+        (3, 2) => 576,
+        _ => 1152,
+    };
+    let len = if layer == 1 {
+        (12 * bitrate / sample_rate + padding) * 4
+    } else {
+        samples / 8 * bitrate / sample_rate + padding
+    } as usize;
+    Some(FrameHeader { version, layer, bitrate, sample_rate, channels, len, samples })
+}
+
+/// Size of an ID3v2 tag at the start, the size is stored as 7 bits per byte
+fn id3_len(bytes: &[u8]) -> usize {
+    if !bytes.starts_with(b"ID3") || bytes.len() < 10 {
+        return 0
+    }
+    let size = bytes[6..10].iter().fold(0usize, |size, byte| size << 7 | (*byte & 0x7F) as usize);
+    let footer = if bytes[5] & 0x10 != 0 { 10 } else { 0 };
+    10 + size + footer
+}
+
+fn probe_mp3(head: &[u8], tail: &[u8], len: usize) -> Result<Option<AudioInfo>, NeedsWholeFile> {
+    let whole_file = head.len() >= len;
+    let mut start = id3_len(head);
+    let search_end = start + MAX_SYNC_SEARCH;
+    // Find the first frame that is followed by another one, so random bytes can't pass as a frame
+    let frame = loop {
+        if start >= search_end {
+            return Ok(None)
+        }
+        let Some(header) = u32_be(head, start) else {
+            // A long ID3 tag can push the first frame past the head
+            return if whole_file { Ok(None) } else { Err(NeedsWholeFile) }
+        };
+        if let Some(frame) = parse_frame_header(header) {
+            let next = u32_be(head, start + frame.len).and_then(parse_frame_header);
+            if next.is_some() || start + frame.len >= len {
+                break frame
+            }
+        }
+        start += 1;
+    };
+
+    // ID3v1 tags are at the end
+    let mut end = len;
+    if end >= start + 128 && tail.len() >= 128 && tail[tail.len() - 128..].starts_with(b"TAG") {
+        end -= 128;
+    }
+    let audio_len = end - start;
+
+    // Side information comes between the header and the Xing header in layer 3
+    let side_info = match (frame.version, frame.channels) {
+        (1, 1) => 17,
+        (1, _) => 32,
+        (_, 1) => 9,
+        _ => 17,
+    };
+    let frame_bytes = &head[start..(start + frame.len).min(head.len())];
+    let xing = frame_bytes.get(4 + side_info..)
+        .filter(|tag| tag.starts_with(b"Xing") || tag.starts_with(b"Info"))
+        .and_then(|tag| if u32_be(tag, 4)? & 1 != 0 { u32_be(tag, 8) } else { None });
+    let vbri = frame_bytes.get(36..)
+        .filter(|tag| tag.starts_with(b"VBRI"))
+        .and_then(|tag| u32_be(tag, 14));
+
+    let (duration, bitrate) = match xing.or(vbri) {
+        Some(frames) => {
+            let duration = Some(Duration::from_secs_f64(frames as f64 * frame.samples as f64 / frame.sample_rate as f64));
+            // The first frame only holds the header
+            (duration, average_bitrate(audio_len - frame.len.min(audio_len), duration))
+        }
+        None if !whole_file => return Err(NeedsWholeFile),
+        None => {
+            // Without a header the frames have to be counted, the bitrate can change between them
+            let mut position = start;
+            let mut samples = 0u64;
+            let mut constant = true;
+            while let Some(next) = u32_be(head, position).and_then(parse_frame_header) {
+                samples += next.samples as u64;
+                position += next.len;
+                constant &= next.bitrate == frame.bitrate;
+            }
+            let duration = Some(Duration::from_secs_f64(samples as f64 / frame.sample_rate as f64));
+            let bitrate = if constant { Some(frame.bitrate) } else { average_bitrate(position.min(end) - start, duration) };
+            (duration, bitrate)
+        }
+    };
+    let codec = match frame.layer {
+        1 => "MP1",
+        2 => "MP2",
+        _ => "MP3",
+    };
+    Ok(Some(AudioInfo { codec: codec.to_owned(), duration, sample_rate: frame.sample_rate, channels: frame.channels, bitrate }))
+}
+
+/// Read the format and length of an Ogg or MP3 file, returns None for anything else
+pub fn probe(bytes: &[u8]) -> Option<AudioInfo> {
+    probe_excerpt(bytes, bytes, bytes.len()).unwrap_or(None)
+}
+
+/// Like probe, but from the first and last bytes of a sound that is `len` bytes long
+pub fn probe_excerpt(head: &[u8], tail: &[u8], len: usize) -> Result<Option<AudioInfo>, NeedsWholeFile> {
+    if head.starts_with(b"OggS") {
+        Ok(probe_ogg(head, tail, len))
+    } else if head.starts_with(b"RIFF") || head.starts_with(b"fLaC") {
+        Ok(None) // Frames could be found inside of the samples of other formats
+    } else {
+        probe_mp3(head, tail, len)
+    }
+}
@@ -0,0 +1,246 @@
+// Writes decoded sounds as 16 bit WAV or FLAC files, for audio editors that can't open Ogg or MP3
+//
+// WAV:
+//   "RIFF" chunk holding a "fmt " chunk with the format and a "data" chunk with the
+//   interleaved little endian samples.
+// FLAC:
+//   "fLaC", a STREAMINFO block, then frames of BLOCK_SIZE samples. Every channel of a
+//   frame is predicted with the fixed polynomial predictor that fits it best and the
+//   residuals are Rice coded. Frames end with a CRC-16, their headers with a CRC-8.
+use super::pcm::Pcm;
+
+const BITS_PER_SAMPLE: u32 = 16;
+const BLOCK_SIZE: usize = 4096;
+// Rice parameters are 4 bits, 15 is the escape code so it can't be used
+const MAX_RICE_PARAMETER: u32 = 14;
+const MAX_FIXED_ORDER: usize = 4;
+// Frame headers have room for up to 8 channels stored independently
+const MAX_FLAC_CHANNELS: usize = 8;
+
+fn to_i16(pcm: &Pcm) -> Vec<i16> {
+    pcm.samples.iter().map(|sample| (sample.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i16).collect()
+}
+
+pub fn to_wav(pcm: &Pcm) -> Vec<u8> {
+    let samples = to_i16(pcm);
+    let channels = pcm.channels as u16;
+    let block_align = channels * (BITS_PER_SAMPLE / 8) as u16;
+    let data_len = samples.len() as u32 * 2;
+
+    let mut output = Vec::with_capacity(44 + data_len as usize);
+    output.extend_from_slice(b"RIFF");
+    output.extend_from_slice(&(36 + data_len).to_le_bytes());
+    output.extend_from_slice(b"WAVE");
+    output.extend_from_slice(b"fmt ");
+    output.extend_from_slice(&16u32.to_le_bytes());
+    output.extend_from_slice(&1u16.to_le_bytes()); // Integer PCM
+    output.extend_from_slice(&channels.to_le_bytes());
+    output.extend_from_slice(&pcm.sample_rate.to_le_bytes());
+    output.extend_from_slice(&(pcm.sample_rate * block_align as u32).to_le_bytes());
+    output.extend_from_slice(&block_align.to_le_bytes());
+    output.extend_from_slice(&(BITS_PER_SAMPLE as u16).to_le_bytes());
+    output.extend_from_slice(b"data");
+    output.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        output.extend_from_slice(&sample.to_le_bytes());
+    }
+    output
+}
+
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u64,
+    len: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, value: u64, bits: u32) {
+        for bit in (0..bits).rev() {
+            self.buffer = self.buffer << 1 | (value >> bit & 1);
+            self.len += 1;
+            if self.len == 8 {
+                self.bytes.push(self.buffer as u8);
+                self.buffer = 0;
+                self.len = 0;
+            }
+        }
+    }
+
+    fn write_unary(&mut self, zeros: u32) {
+        for _ in 0..zeros {
+            self.write(0, 1);
+        }
+        self.write(1, 1);
+    }
+
+    fn align(&mut self) {
+        if self.len > 0 {
+            self.write(0, 8 - self.len);
+        }
+    }
+}
+
+fn crc8(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0u8, |crc, byte| {
+        (0..8).fold(crc ^ byte, |crc, _| if crc & 0x80 != 0 { crc << 1 ^ 0x07 } else { crc << 1 })
+    })
+}
+
+fn crc16(bytes: &[u8]) -> u16 {
+    bytes.iter().fold(0u16, |crc, byte| {
+        (0..8).fold(crc ^ (*byte as u16) << 8, |crc, _| if crc & 0x8000 != 0 { crc << 1 ^ 0x8005 } else { crc << 1 })
+    })
+}
+
+/// Frame numbers are written like UTF-8 characters
+fn write_utf8_number(writer: &mut BitWriter, number: u32) {
+    if number < 0x80 {
+        writer.write(number as u64, 8);
+        return
+    }
+    let mut continuation = Vec::new();
+    let mut rest = number;
+    while rest >= 1 << (6 - continuation.len()) {
+        continuation.push(0x80 | (rest & 0x3F) as u64);
+        rest >>= 6;
+    }
+    let bytes = continuation.len() as u32 + 1;
+    let lead = (0xFF00u64 >> bytes) & 0xFF;
+    writer.write(lead | rest as u64, 8);
+    for byte in continuation.into_iter().rev() {
+        writer.write(byte, 8);
+    }
+}
+
+/// Residuals left after predicting each sample from the ones before it with a fixed polynomial
+fn fixed_residuals(samples: &[i32], order: usize) -> Vec<i32> {
+    let mut residuals = samples.to_vec();
+    for _ in 0..order {
+        residuals = residuals.windows(2).map(|pair| pair[1] - pair[0]).collect();
+    }
+    residuals
+}
+
+fn zigzag(residual: i32) -> u32 {
+    (residual << 1 ^ residual >> 31) as u32
+}
+
+/// Rice parameter that codes the residuals in the fewest bits, with the amount of bits
+fn best_rice_parameter(residuals: &[i32]) -> (u32, u64) {
+    (0..=MAX_RICE_PARAMETER).map(|parameter| {
+        let bits = residuals.iter().map(|residual| (zigzag(*residual) >> parameter) as u64 + 1 + parameter as u64).sum();
+        (parameter, bits)
+    }).min_by_key(|(_, bits)| *bits).unwrap_or((0, 0))
+}
+
+fn write_subframe(writer: &mut BitWriter, samples: &[i32]) {
+    let order = (0..=MAX_FIXED_ORDER.min(samples.len().saturating_sub(1)))
+        .min_by_key(|order| best_rice_parameter(&fixed_residuals(samples, *order)).1)
+        .unwrap_or(0);
+    let residuals = fixed_residuals(samples, order);
+    let (parameter, _) = best_rice_parameter(&residuals);
+
+    writer.write(0, 1); // Padding
+    writer.write(0b001000 | order as u64, 6); // Fixed predictor
+    writer.write(0, 1); // No wasted bits
+    for sample in &samples[..order] {
+        writer.write(*sample as u16 as u64, BITS_PER_SAMPLE); // Warm up samples
+    }
+    writer.write(0, 2); // Rice coding with 4 bit parameters
+    writer.write(0, 4); // A single partition
+    writer.write(parameter as u64, 4);
+    for residual in residuals {
+        let value = zigzag(residual);
+        writer.write_unary(value >> parameter);
+        writer.write(value as u64 & ((1 << parameter) - 1), parameter);
+    }
+}
+
+pub fn to_flac(pcm: &Pcm) -> Result<Vec<u8>, String> {
+    if pcm.channels == 0 || pcm.channels > MAX_FLAC_CHANNELS {
+        return Err(format!("FLAC files can't have {} channels", pcm.channels))
+    }
+    let samples = to_i16(pcm);
+    let channels = pcm.channels;
+    let frames = samples.len() / channels;
+
+    let mut output = b"fLaC".to_vec();
+    let mut info = BitWriter::default();
+    info.write(1, 1); // Last metadata block
+    info.write(0, 7); // STREAMINFO
+    info.write(34, 24);
+    info.write(BLOCK_SIZE as u64, 16); // Smallest block size, the last block doesn't count
+    info.write(BLOCK_SIZE as u64, 16);
+    info.write(0, 24); // Frame sizes are unknown
+    info.write(0, 24);
+    info.write(pcm.sample_rate as u64, 20);
+    info.write(channels as u64 - 1, 3);
+    info.write(BITS_PER_SAMPLE as u64 - 1, 5);
+    info.write(frames as u64, 36);
+    info.write(0, 64); // No MD5 of the samples
+    info.write(0, 64);
+    output.extend_from_slice(&info.bytes);
+
+    for (number, start) in (0..frames).step_by(BLOCK_SIZE).enumerate() {
+        let block_len = BLOCK_SIZE.min(frames - start);
+        let mut frame = BitWriter::default();
+        frame.write(0b11111111111110, 14); // Sync code
+        frame.write(0, 1);
+        frame.write(0, 1); // Fixed block size
+        frame.write(0b0111, 4); // Block size in the 16 bits after the frame number
+        frame.write(0, 4); // Sample rate from STREAMINFO
+        frame.write(channels as u64 - 1, 4); // Channels are stored independently
+        frame.write(0b100, 3); // 16 bits per sample
+        frame.write(0, 1);
+        write_utf8_number(&mut frame, number as u32);
+        frame.write(block_len as u64 - 1, 16);
+        let crc = crc8(&frame.bytes);
+        frame.write(crc as u64, 8);
+
+        for channel in 0..channels {
+            let channel_samples: Vec<i32> = (start..start + block_len)
+                .map(|index| samples[index * channels + channel] as i32)
+                .collect();
+            write_subframe(&mut frame, &channel_samples);
+        }
+        frame.align();
+        let crc = crc16(&frame.bytes);
+        frame.write(crc as u64, 16);
+        output.extend_from_slice(&frame.bytes);
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flac_decodes_to_the_same_samples() {
+        // More than one block, with a channel that is hard to predict and one that is silent at the end
+        let frames = BLOCK_SIZE + 1000;
+        let samples = (0..frames).flat_map(|frame| {
+            let time = frame as f32 / 44100.0;
+            let noise = ((frame * 7919) % 201) as f32 / 1000.0 - 0.1;
+            [(time * 440.0 * std::f32::consts::TAU).sin() * 0.8 + noise, if frame < 3000 { noise * 5.0 } else { 0.0 }]
+        }).collect();
+        let pcm = Pcm { sample_rate: 44100, channels: 2, samples };
+
+        let decoded = Pcm::decode(&to_flac(&pcm).unwrap()).unwrap();
+        assert_eq!(decoded.sample_rate, 44100);
+        assert_eq!(decoded.channels, 2);
+        assert_eq!(decoded.frames(), frames);
+        for (index, (decoded, sample)) in decoded.samples.iter().zip(to_i16(&pcm)).enumerate() {
+            assert_eq!((decoded * 32768.0).round() as i16, sample, "sample {}", index);
+        }
+    }
+
+    #[test]
+    fn flac_needs_one_to_eight_channels() {
+        let pcm = |channels| Pcm { sample_rate: 44100, channels, samples: vec![0.0; channels * 10] };
+        assert!(to_flac(&pcm(0)).is_err());
+        assert!(to_flac(&pcm(9)).is_err());
+        assert!(Pcm::decode(&to_flac(&pcm(8)).unwrap()).is_ok_and(|decoded| decoded.channels == 8));
+    }
+}
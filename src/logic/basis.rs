@@ -0,0 +1,495 @@
+// Decoder for ETC1S textures stored with BasisLZ supercompression in KTX2 files
+//
+// The supercompression global data holds codebooks shared by every image:
+//   u16            endpoint count
+//   u16            selector count
+//   u32 * 4        endpoints, selectors, tables and extended data lengths
+//   [u32; 5]       per image: flags, rgb slice offset and length, alpha slice offset and length
+//   [u8]           endpoints, selectors and tables, all huffman coded
+//
+// Each slice is a stream of blocks referencing the codebooks, ported from the basis universal transcoder
+use std::io::{Error, ErrorKind, Result};
+
+use image::RgbaImage;
+
+const HEADER_LEN: usize = 20;
+const IMAGE_DESC_LEN: usize = 20;
+
+// Huffman code lengths are themselves huffman coded using these codes
+const MAX_CODE_SIZE: usize = 16;
+const TOTAL_CODE_LENGTH_CODES: usize = 21;
+const SMALL_ZERO_RUN_CODE: u32 = 17;
+const BIG_ZERO_RUN_CODE: u32 = 18;
+const SMALL_REPEAT_CODE: u32 = 19;
+const SORTED_CODE_LENGTH_CODES: [usize; TOTAL_CODE_LENGTH_CODES] = [17, 18, 19, 20, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15, 16];
+
+// Endpoint palette deltas use a different model depending on the previous value
+const COLOR5_PAL0_PREV_HI: u8 = 9;
+const COLOR5_PAL1_PREV_HI: u8 = 21;
+
+const ENDPOINT_PRED_REPEAT_LAST_SYMBOL: u32 = 256;
+const ENDPOINT_PRED_MIN_REPEAT_COUNT: u32 = 3;
+const ENDPOINT_PRED_COUNT_VLC_BITS: u32 = 4;
+const SELECTOR_HISTORY_BUF_RLE_COUNT_THRESH: u32 = 3;
+const SELECTOR_HISTORY_BUF_RLE_COUNT_TOTAL: u32 = 64;
+
+const ETC1_INTEN_TABLES: [[i32; 4]; 8] = [
+    [-8, -2, 2, 8], [-17, -5, 5, 17], [-29, -9, 9, 29], [-42, -13, 13, 42],
+    [-60, -18, 18, 60], [-80, -24, 24, 80], [-106, -33, 33, 106], [-183, -47, 47, 183],
+];
+
+fn invalid(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Result<u32> {
+    let field = bytes.get(offset..offset + 4).ok_or_else(|| invalid("BasisLZ data is truncated"))?;
+    Ok(u32::from_le_bytes([field[0], field[1], field[2], field[3]]))
+}
+
+// Reads bits starting from the least significant bit, reading past the end gives zeros like the reference decoder
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    buffer: u64,
+    buffer_len: u32,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(bytes: &'a [u8]) -> BitReader<'a> {
+        BitReader { bytes, position: 0, buffer: 0, buffer_len: 0 }
+    }
+
+    fn fill(&mut self, bits: u32) {
+        while self.buffer_len < bits {
+            let byte = self.bytes.get(self.position).copied().unwrap_or(0);
+            self.position += 1;
+            self.buffer |= (byte as u64) << self.buffer_len;
+            self.buffer_len += 8;
+        }
+    }
+
+    fn bits(&mut self, bits: u32) -> u32 {
+        if bits == 0 {
+            return 0
+        }
+        self.fill(bits);
+        let value = (self.buffer & ((1 << bits) - 1)) as u32;
+        self.buffer >>= bits;
+        self.buffer_len -= bits;
+        value
+    }
+
+    fn vlc(&mut self, chunk_bits: u32) -> u32 {
+        let chunk_size = 1 << chunk_bits;
+        let mut value = 0;
+        let mut shift = 0;
+        loop {
+            let chunk = self.bits(chunk_bits + 1);
+            value |= (chunk & (chunk_size - 1)) << shift;
+            shift += chunk_bits;
+            if chunk & chunk_size == 0 || shift >= 32 {
+                return value
+            }
+        }
+    }
+
+    fn huffman(&mut self, table: &HuffmanTable) -> Result<u32> {
+        table.decode(self)
+    }
+
+    fn huffman_table(&mut self) -> Result<HuffmanTable> {
+        let total_symbols = self.bits(14) as usize;
+        if total_symbols == 0 {
+            return Ok(HuffmanTable::default())
+        }
+
+        let code_length_codes = self.bits(5) as usize;
+        if code_length_codes == 0 || code_length_codes > TOTAL_CODE_LENGTH_CODES {
+            return Err(invalid("Invalid huffman table"));
+        }
+        let mut code_length_sizes = [0u8; TOTAL_CODE_LENGTH_CODES];
+        for code in SORTED_CODE_LENGTH_CODES.iter().take(code_length_codes) {
+            code_length_sizes[*code] = self.bits(3) as u8;
+        }
+        let code_length_table = HuffmanTable::new(&code_length_sizes)?;
+
+        let mut code_sizes = vec![0u8; total_symbols];
+        let mut current = 0;
+        while current < total_symbols {
+            let code = self.huffman(&code_length_table)?;
+            match code {
+                0..=16 => {
+                    code_sizes[current] = code as u8;
+                    current += 1;
+                }
+                SMALL_ZERO_RUN_CODE => current += self.bits(3) as usize + 3,
+                BIG_ZERO_RUN_CODE => current += self.bits(7) as usize + 11,
+                _ => {
+                    let repeat = if code == SMALL_REPEAT_CODE {
+                        self.bits(2) as usize + 3
+                    } else {
+                        self.bits(7) as usize + 7
+                    };
+                    let previous = match current.checked_sub(1).map(|i| code_sizes[i]) {
+                        Some(size) if size != 0 => size,
+                        _ => return Err(invalid("Invalid huffman table")),
+                    };
+                    if current + repeat > total_symbols {
+                        return Err(invalid("Invalid huffman table"));
+                    }
+                    code_sizes[current..current + repeat].fill(previous);
+                    current += repeat;
+                }
+            }
+        }
+        if current != total_symbols {
+            return Err(invalid("Invalid huffman table"));
+        }
+
+        HuffmanTable::new(&code_sizes)
+    }
+}
+
+// Canonical huffman codes, assigned in symbol order for each code size
+#[derive(Default)]
+struct HuffmanTable {
+    counts: [u16; MAX_CODE_SIZE + 1],
+    symbols: Vec<u16>,
+}
+
+impl HuffmanTable {
+    fn new(code_sizes: &[u8]) -> Result<HuffmanTable> {
+        let mut counts = [0u16; MAX_CODE_SIZE + 1];
+        for size in code_sizes {
+            if *size as usize > MAX_CODE_SIZE {
+                return Err(invalid("Invalid huffman code size"));
+            }
+            counts[*size as usize] += 1;
+        }
+        counts[0] = 0;
+
+        let mut symbols = Vec::new();
+        for size in 1..=MAX_CODE_SIZE {
+            for (symbol, code_size) in code_sizes.iter().enumerate() {
+                if *code_size as usize == size {
+                    symbols.push(symbol as u16);
+                }
+            }
+        }
+        if symbols.is_empty() {
+            return Err(invalid("Huffman table has no symbols"));
+        }
+        Ok(HuffmanTable { counts, symbols })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    fn decode(&self, reader: &mut BitReader) -> Result<u32> {
+        // Codes are stored most significant bit first
+        let mut code = 0;
+        let mut first = 0;
+        let mut index = 0;
+        for size in 1..=MAX_CODE_SIZE {
+            code |= reader.bits(1) as usize;
+            let count = self.counts[size] as usize;
+            if code < first + count {
+                return Ok(self.symbols[index + code - first] as u32)
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err(invalid("Invalid huffman code"))
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Endpoint {
+    color: [u8; 3],
+    inten: u8,
+}
+
+impl Endpoint {
+    fn colors(&self) -> [[u8; 3]; 4] {
+        let base = self.color.map(|c| ((c << 3) | (c >> 2)) as i32);
+        ETC1_INTEN_TABLES[self.inten as usize].map(|modifier| base.map(|c| (c + modifier).clamp(0, 255) as u8))
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct ImageDesc {
+    pub rgb_slice: (usize, usize),
+    pub alpha_slice: Option<(usize, usize)>,
+}
+
+pub struct Codebook {
+    endpoints: Vec<Endpoint>,
+    // Each selector is 4 rows of 2 bit indices into the intensity table
+    selectors: Vec<[u8; 4]>,
+    endpoint_pred_model: HuffmanTable,
+    delta_endpoint_model: HuffmanTable,
+    selector_model: HuffmanTable,
+    selector_history_rle_model: HuffmanTable,
+    selector_history_size: usize,
+    pub images: Vec<ImageDesc>,
+}
+
+fn decode_endpoints(bytes: &[u8], count: usize) -> Result<Vec<Endpoint>> {
+    let mut reader = BitReader::new(bytes);
+    let color_models = [reader.huffman_table()?, reader.huffman_table()?, reader.huffman_table()?];
+    let inten_model = reader.huffman_table()?;
+    if color_models.iter().any(|model| model.is_empty()) || inten_model.is_empty() {
+        return Err(invalid("Invalid endpoint codebook"));
+    }
+    let grayscale = reader.bits(1) != 0;
+
+    let mut endpoints = Vec::with_capacity(count);
+    let mut previous_color = [16u8; 3];
+    let mut previous_inten = 0;
+    for _ in 0..count {
+        let inten = ((reader.huffman(&inten_model)? + previous_inten) & 7) as u8;
+        previous_inten = inten as u32;
+
+        let channels = if grayscale { 1 } else { 3 };
+        for channel in previous_color.iter_mut().take(channels) {
+            let model = if *channel <= COLOR5_PAL0_PREV_HI {
+                &color_models[0]
+            } else if *channel <= COLOR5_PAL1_PREV_HI {
+                &color_models[1]
+            } else {
+                &color_models[2]
+            };
+            *channel = ((*channel as u32 + reader.huffman(model)?) & 31) as u8;
+        }
+        if grayscale {
+            previous_color = [previous_color[0]; 3];
+        }
+        endpoints.push(Endpoint { color: previous_color, inten });
+    }
+    Ok(endpoints)
+}
+
+fn decode_selectors(bytes: &[u8], count: usize) -> Result<Vec<[u8; 4]>> {
+    let mut reader = BitReader::new(bytes);
+    if reader.bits(1) != 0 || reader.bits(1) != 0 {
+        return Err(Error::new(ErrorKind::Unsupported, "Global selector codebooks are not supported"));
+    }
+
+    let mut selectors = Vec::with_capacity(count);
+    if reader.bits(1) != 0 {
+        // Raw selectors
+        for _ in 0..count {
+            selectors.push([0; 4].map(|_| reader.bits(8) as u8));
+        }
+        return Ok(selectors)
+    }
+
+    // Each selector is stored as the difference to the previous one
+    let delta_model = reader.huffman_table()?;
+    if count > 1 && delta_model.is_empty() {
+        return Err(invalid("Invalid selector codebook"));
+    }
+    let mut previous = [0u8; 4];
+    for i in 0..count {
+        for byte in previous.iter_mut() {
+            *byte = if i == 0 {
+                reader.bits(8) as u8
+            } else {
+                reader.huffman(&delta_model)? as u8 ^ *byte
+            };
+        }
+        selectors.push(previous);
+    }
+    Ok(selectors)
+}
+
+impl Codebook {
+    /// Parse the supercompression global data, image_count is the number of images in the whole texture
+    pub fn parse(global_data: &[u8], image_count: usize) -> Result<Codebook> {
+        if global_data.len() < HEADER_LEN {
+            return Err(invalid("BasisLZ data is truncated"));
+        }
+        let endpoint_count = u16::from_le_bytes([global_data[0], global_data[1]]) as usize;
+        let selector_count = u16::from_le_bytes([global_data[2], global_data[3]]) as usize;
+        let endpoints_len = read_u32(global_data, 4)? as usize;
+        let selectors_len = read_u32(global_data, 8)? as usize;
+        let tables_len = read_u32(global_data, 12)? as usize;
+        if endpoint_count == 0 || selector_count == 0 {
+            return Err(invalid("BasisLZ codebook is empty"));
+        }
+
+        let mut images = Vec::with_capacity(image_count);
+        for i in 0..image_count {
+            let offset = HEADER_LEN + i * IMAGE_DESC_LEN;
+            let alpha_len = read_u32(global_data, offset + 16)? as usize;
+            images.push(ImageDesc {
+                rgb_slice: (read_u32(global_data, offset + 4)? as usize, read_u32(global_data, offset + 8)? as usize),
+                alpha_slice: if alpha_len > 0 { Some((read_u32(global_data, offset + 12)? as usize, alpha_len)) } else { None },
+            });
+        }
+
+        let start = HEADER_LEN + image_count * IMAGE_DESC_LEN;
+        let section = |offset: usize, len: usize| {
+            global_data.get(start + offset..start + offset + len).ok_or_else(|| invalid("BasisLZ data is truncated"))
+        };
+        let endpoints = decode_endpoints(section(0, endpoints_len)?, endpoint_count)?;
+        let selectors = decode_selectors(section(endpoints_len, selectors_len)?, selector_count)?;
+
+        let mut reader = BitReader::new(section(endpoints_len + selectors_len, tables_len)?);
+        let endpoint_pred_model = reader.huffman_table()?;
+        let delta_endpoint_model = reader.huffman_table()?;
+        let selector_model = reader.huffman_table()?;
+        let selector_history_rle_model = reader.huffman_table()?;
+        let selector_history_size = reader.bits(13) as usize;
+        if endpoint_pred_model.is_empty() || delta_endpoint_model.is_empty() || selector_model.is_empty()
+            || selector_history_rle_model.is_empty() || selector_history_size == 0 {
+            return Err(invalid("Invalid BasisLZ tables"));
+        }
+
+        Ok(Codebook {
+            endpoints,
+            selectors,
+            endpoint_pred_model,
+            delta_endpoint_model,
+            selector_model,
+            selector_history_rle_model,
+            selector_history_size,
+            images,
+        })
+    }
+
+    /// Decode a slice into the image, alpha slices are stored as grayscale and go into the alpha channel
+    fn decode_slice(&self, slice: &[u8], image: &mut RgbaImage, alpha: bool) -> Result<()> {
+        let (width, height) = (image.width() as usize, image.height() as usize);
+        let (blocks_x, blocks_y) = (width.div_ceil(4), height.div_ceil(4));
+        let mut reader = BitReader::new(slice);
+
+        // Prediction bits and endpoints of the previous and current row of blocks
+        let mut pred_bits = [vec![0u32; blocks_x], vec![0u32; blocks_x]];
+        let mut row_endpoints = [vec![0usize; blocks_x], vec![0usize; blocks_x]];
+
+        let mut selector_history = vec![0usize; self.selector_history_size];
+        let mut history_rover = selector_history.len() / 2;
+        let history_symbol = self.selectors.len() + self.selector_history_size;
+        let mut selector_rle_count = 0;
+
+        let mut current_pred_bits = 0;
+        let mut previous_pred_symbol = 0;
+        let mut pred_repeat_count = 0;
+        let mut previous_endpoint = 0;
+
+        for block_y in 0..blocks_y {
+            let row = block_y & 1;
+            for block_x in 0..blocks_x {
+                // One symbol predicts the endpoints of a 2x2 group of blocks
+                if block_x & 1 == 0 {
+                    if block_y & 1 == 0 {
+                        if pred_repeat_count > 0 {
+                            pred_repeat_count -= 1;
+                            current_pred_bits = previous_pred_symbol;
+                        } else {
+                            current_pred_bits = reader.huffman(&self.endpoint_pred_model)?;
+                            if current_pred_bits == ENDPOINT_PRED_REPEAT_LAST_SYMBOL {
+                                pred_repeat_count = reader.vlc(ENDPOINT_PRED_COUNT_VLC_BITS) + ENDPOINT_PRED_MIN_REPEAT_COUNT - 1;
+                                current_pred_bits = previous_pred_symbol;
+                            } else {
+                                previous_pred_symbol = current_pred_bits;
+                            }
+                        }
+                        pred_bits[row ^ 1][block_x] = current_pred_bits >> 4;
+                    } else {
+                        current_pred_bits = pred_bits[row][block_x];
+                    }
+                }
+
+                let pred = current_pred_bits & 3;
+                current_pred_bits >>= 2;
+                let endpoint = match pred {
+                    0 if block_x > 0 => previous_endpoint,
+                    1 if block_y > 0 => row_endpoints[row ^ 1][block_x],
+                    2 if block_x > 0 && block_y > 0 => row_endpoints[row ^ 1][block_x - 1],
+                    3 => {
+                        let endpoint = reader.huffman(&self.delta_endpoint_model)? as usize + previous_endpoint;
+                        if endpoint >= self.endpoints.len() { endpoint - self.endpoints.len() } else { endpoint }
+                    }
+                    _ => return Err(invalid("Invalid endpoint prediction")),
+                };
+                row_endpoints[row][block_x] = endpoint;
+                previous_endpoint = endpoint;
+
+                // Selectors are either new, or reused from a history buffer with optional runs
+                let symbol = if selector_rle_count > 0 {
+                    selector_rle_count -= 1;
+                    self.selectors.len()
+                } else {
+                    let symbol = reader.huffman(&self.selector_model)? as usize;
+                    if symbol == history_symbol {
+                        let run = reader.huffman(&self.selector_history_rle_model)?;
+                        selector_rle_count = if run == SELECTOR_HISTORY_BUF_RLE_COUNT_TOTAL - 1 {
+                            reader.vlc(7) + SELECTOR_HISTORY_BUF_RLE_COUNT_THRESH
+                        } else {
+                            run + SELECTOR_HISTORY_BUF_RLE_COUNT_THRESH
+                        } as usize;
+                        if selector_rle_count > blocks_x * blocks_y {
+                            return Err(invalid("Invalid selector run"));
+                        }
+                        selector_rle_count -= 1;
+                        self.selectors.len()
+                    } else {
+                        symbol
+                    }
+                };
+                let selector = if symbol >= self.selectors.len() {
+                    let index = symbol - self.selectors.len();
+                    let selector = *selector_history.get(index).ok_or_else(|| invalid("Invalid selector history index"))?;
+                    if index != 0 {
+                        selector_history.swap(index / 2, index);
+                    }
+                    selector
+                } else {
+                    selector_history[history_rover] = symbol;
+                    history_rover += 1;
+                    if history_rover == selector_history.len() {
+                        history_rover = selector_history.len() / 2;
+                    }
+                    symbol
+                };
+
+                let (Some(endpoint), Some(selector)) = (self.endpoints.get(endpoint), self.selectors.get(selector)) else {
+                    return Err(invalid("Block references a missing codebook entry"));
+                };
+                let colors = endpoint.colors();
+                for y in 0..4.min(height - block_y * 4) {
+                    for x in 0..4.min(width - block_x * 4) {
+                        let color = colors[((selector[y] >> (x * 2)) & 3) as usize];
+                        let pixel = image.get_pixel_mut((block_x * 4 + x) as u32, (block_y * 4 + y) as u32);
+                        if alpha {
+                            pixel[3] = color[1];
+                        } else {
+                            *pixel = image::Rgba([color[0], color[1], color[2], 255]);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Decode an image, slice offsets in the image description are relative to the level data
+    pub fn decode_image(&self, image: usize, level_data: &[u8], width: u32, height: u32) -> Result<RgbaImage> {
+        let desc = self.images.get(image).ok_or_else(|| invalid("BasisLZ image description is missing"))?;
+        let slice = |(offset, len): (usize, usize)| {
+            level_data.get(offset..offset + len).ok_or_else(|| invalid("BasisLZ slice is out of bounds"))
+        };
+
+        let mut output = RgbaImage::new(width, height);
+        self.decode_slice(slice(desc.rgb_slice)?, &mut output, false)?;
+        if let Some(alpha_slice) = desc.alpha_slice {
+            self.decode_slice(slice(alpha_slice)?, &mut output, true)?;
+        }
+        Ok(output)
+    }
+}
@@ -0,0 +1,35 @@
+// Conversions that can be applied to assets when extracting them
+use std::io::Cursor;
+
+use super::{audio_export, decode_image, mesh, mesh_export, pcm, rbxm, rbxmx};
+
+/// Formats assets of a category can be converted to, the format name is also used as the extension
+pub fn get_conversions(category: &str) -> Vec<&'static str> {
+    match category {
+        "ktx-files" => vec!["png"],
+        "rbxm-files" => vec!["rbxmx"],
+        "meshes" => vec!["obj", "gltf"],
+        "sounds" | "music" => vec!["wav", "flac"],
+        _ => Vec::new(),
+    }
+}
+
+pub fn convert(bytes: &[u8], format: &str) -> Result<Vec<u8>, String> {
+    match format {
+        "png" => {
+            let image = decode_image(bytes, None).map_err(|e| e.to_string())?;
+            let mut output = Cursor::new(Vec::new());
+            image.write_to(&mut output, image::ImageFormat::Png).map_err(|e| e.to_string())?;
+            Ok(output.into_inner())
+        }
+        "rbxmx" => {
+            let model = rbxm::Model::parse(bytes).map_err(|e| e.to_string())?;
+            Ok(rbxmx::write(&model).into_bytes())
+        }
+        "obj" => Ok(mesh_export::to_obj(&mesh::Mesh::parse(bytes).map_err(|e| e.to_string())?).into_bytes()),
+        "gltf" => Ok(mesh_export::to_gltf(&mesh::Mesh::parse(bytes).map_err(|e| e.to_string())?).into_bytes()),
+        "wav" => Ok(audio_export::to_wav(&pcm::Pcm::decode(bytes)?)),
+        "flac" => audio_export::to_flac(&pcm::Pcm::decode(bytes)?),
+        _ => Err(format!("Unknown format '{}'", format)),
+    }
+}
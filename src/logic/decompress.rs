@@ -0,0 +1,124 @@
+use std::io::{Error, ErrorKind, Read, Result};
+
+use flate2::read::{GzDecoder, ZlibDecoder};
+
+const GZIP_MAGIC: &[u8] = &[0x1F, 0x8B];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xB5, 0x2F, 0xFD];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Encoding {
+    Gzip,
+    Deflate,
+    Zstd,
+    Brotli,
+}
+
+impl Encoding {
+    fn from_name(name: &str) -> Option<Encoding> {
+        match name.trim().to_lowercase().as_str() {
+            "gzip" | "x-gzip" => Some(Encoding::Gzip),
+            "deflate" => Some(Encoding::Deflate),
+            "zstd" => Some(Encoding::Zstd),
+            "br" => Some(Encoding::Brotli),
+            _ => None,
+        }
+    }
+}
+
+/// Get the encodings from a Content-Encoding header, in the order they were applied
+pub fn parse_content_encoding(header: &str) -> Result<Vec<Encoding>> {
+    let mut encodings = Vec::new();
+    for name in header.split(',') {
+        if name.trim().is_empty() || name.trim().eq_ignore_ascii_case("identity") {
+            continue;
+        }
+        match Encoding::from_name(name) {
+            Some(encoding) => encodings.push(encoding),
+            None => return Err(Error::new(ErrorKind::Unsupported, format!("Unsupported content encoding '{}'", name.trim()))),
+        }
+    }
+    Ok(encodings)
+}
+
+/// Detect an encoding from the start of the bytes, brotli has no magic so it can't be detected
+pub fn sniff_encoding(bytes: &[u8]) -> Option<Encoding> {
+    if bytes.starts_with(GZIP_MAGIC) {
+        Some(Encoding::Gzip)
+    } else if bytes.starts_with(ZSTD_MAGIC) {
+        Some(Encoding::Zstd)
+    } else {
+        None
+    }
+}
+
+/// How much of a stream to decode
+#[derive(Debug, Clone, Copy)]
+pub enum Limit {
+    /// Only the start is wanted, the bytes may be the start of the stream too
+    Prefix(usize),
+    /// The whole stream, it is an error if it decodes to more than this many bytes
+    Max(usize),
+}
+
+fn decoder<'a>(encoding: Encoding, reader: Box<dyn Read + 'a>) -> Result<Box<dyn Read + 'a>> {
+    Ok(match encoding {
+        Encoding::Gzip => Box::new(GzDecoder::new(reader)),
+        Encoding::Deflate => Box::new(ZlibDecoder::new(reader)),
+        Encoding::Zstd => Box::new(ruzstd::decoding::StreamingDecoder::new(reader)
+            .map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))?),
+        Encoding::Brotli => Box::new(brotli_decompressor::Decompressor::new(reader, 4096)),
+    })
+}
+
+/// Decode the bytes, encodings are undone in reverse order.
+/// A few compressed bytes can decode to gigabytes, so there is always a limit.
+pub fn decode(bytes: &[u8], encodings: &[Encoding], limit: Limit) -> Result<Vec<u8>> {
+    let mut reader: Box<dyn Read> = Box::new(bytes);
+    for encoding in encodings.iter().rev() {
+        reader = decoder(*encoding, reader)?;
+    }
+
+    let mut output = Vec::new();
+    match limit {
+        Limit::Prefix(limit) => {
+            let mut buffer = [0; 4096];
+            while output.len() < limit {
+                match reader.read(&mut buffer) {
+                    Ok(0) => break,
+                    Ok(read) => output.extend_from_slice(&buffer[..read]),
+                    // The stream is cut off, keep what has been decoded so far
+                    Err(_) if !output.is_empty() => break,
+                    Err(e) => return Err(e),
+                }
+            }
+            output.truncate(limit);
+        }
+        Limit::Max(max) => {
+            reader.take(max as u64 + 1).read_to_end(&mut output)?;
+            if output.len() > max {
+                return Err(Error::new(ErrorKind::InvalidData, format!("Decompressed data is larger than {} bytes", max)))
+            }
+        }
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn gzip(bytes: &[u8]) -> Vec<u8> {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+        encoder.write_all(bytes).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn stops_at_the_limit() {
+        let compressed = gzip(&vec![0; 1024 * 1024]);
+        assert_eq!(decode(&compressed, &[Encoding::Gzip], Limit::Prefix(1000)).unwrap().len(), 1000);
+        assert!(decode(&compressed, &[Encoding::Gzip], Limit::Max(1000)).is_err());
+        assert_eq!(decode(&compressed, &[Encoding::Gzip], Limit::Max(1024 * 1024)).unwrap().len(), 1024 * 1024);
+    }
+}
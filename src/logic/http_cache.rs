@@ -0,0 +1,134 @@
+// Parser for the entries inside of the client's http cache directory
+//
+// Layout of an entry (all integers are little endian):
+//   "RBXH"         magic
+//   u32            size of the entry header after this field
+//   u32            url length
+//   [u8]           url
+//   u8             unknown
+//   u32            http status
+//   u32            response headers length
+//   u32            hash of the body (not checked)
+//   u32            body length
+//   [u8; 8]        unknown
+//   [u8]           response headers, "name: value" lines
+//   [u8]           body
+use std::io::{Error, ErrorKind, Result};
+
+const MAGIC: &[u8; 4] = b"RBXH";
+const RESERVED_LEN: usize = 8;
+
+#[derive(Debug, Clone)]
+pub struct CacheEntry {
+    pub url: String,
+    pub status: u32,
+    pub headers: Vec<(String, String)>,
+    pub body_offset: usize,
+    pub body_len: usize,
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self.position.checked_add(len).filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| invalid("Cache entry is truncated"))?;
+        let slice = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+}
+
+fn invalid(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
+
+fn parse_headers(bytes: &[u8]) -> Result<Vec<(String, String)>> {
+    let text = std::str::from_utf8(bytes).map_err(|_| invalid("Response headers are not text"))?;
+    let mut headers = Vec::new();
+    for line in text.split(['\r', '\n']) {
+        // Skip blank lines and the status line if the client stored one
+        if line.is_empty() || line.starts_with("HTTP/") {
+            continue;
+        }
+        match line.split_once(':') {
+            Some((name, value)) => headers.push((name.trim().to_lowercase(), value.trim().to_owned())),
+            None => return Err(invalid("Malformed response header")),
+        }
+    }
+    Ok(headers)
+}
+
+/// Check if the bytes start like a cache entry, the rest may still fail to parse
+pub fn is_cache_entry(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC)
+}
+
+impl CacheEntry {
+    /// Parse the entry header, the bytes only need to contain the header, not the whole body
+    pub fn parse(bytes: &[u8]) -> Result<CacheEntry> {
+        if !is_cache_entry(bytes) {
+            return Err(invalid("Not a cache entry"));
+        }
+        let mut reader = Reader { bytes, position: MAGIC.len() };
+
+        let _header_size = reader.u32()?;
+        let url_len = reader.u32()? as usize;
+        let url = String::from_utf8_lossy(reader.take(url_len)?).to_string();
+        let _unknown = reader.u8()?;
+        let status = reader.u32()?;
+        let headers_len = reader.u32()? as usize;
+        let _hash = reader.u32()?;
+        let body_len = reader.u32()? as usize;
+        reader.take(RESERVED_LEN)?;
+
+        if !(100..600).contains(&status) {
+            return Err(invalid("Invalid http status"));
+        }
+
+        let headers = parse_headers(reader.take(headers_len)?)?;
+
+        Ok(CacheEntry {
+            url,
+            status,
+            headers,
+            body_offset: reader.position,
+            body_len,
+        })
+    }
+
+    /// Check if the response was successful, other responses don't have an asset as the body
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Get a response header, names are case insensitive
+    pub fn header(&self, name: &str) -> Option<&str> {
+        let name = name.to_lowercase();
+        self.headers.iter().find(|(key, _)| *key == name).map(|(_, value)| value.as_str())
+    }
+
+    /// Response headers joined back together as text
+    pub fn header_text(&self) -> String {
+        self.headers.iter().map(|(name, value)| format!("{}: {}\n", name, value)).collect()
+    }
+
+    /// Slice the body out of the entry, clamped to the bytes that are available
+    pub fn body<'a>(&self, bytes: &'a [u8]) -> &'a [u8] {
+        let start = self.body_offset.min(bytes.len());
+        let end = self.body_offset.saturating_add(self.body_len).min(bytes.len());
+        &bytes[start..end]
+    }
+}
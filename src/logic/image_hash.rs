@@ -0,0 +1,144 @@
+// Perceptual hashes for finding images that look alike
+//
+// Each hash is 64 bits from an 8x8 grid of a shrunk greyscale copy of the image:
+//   aHash: whether each pixel is brighter than the average
+//   dHash: whether each pixel is brighter than the one to its right, from a 9x8 copy
+//   pHash: whether each of the lowest frequencies of a 32x32 DCT is above their median
+// Resized or recoloured copies of an image only flip a few bits, so the amount of bits
+// that differ (the Hamming distance) tells how alike two images are.
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use image::{imageops, GrayImage, Luma, RgbaImage};
+use serde::{Deserialize, Serialize};
+
+const DCT_SIZE: usize = 32;
+const HASH_SIZE: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ImageHash {
+    pub average: u64,
+    pub difference: u64,
+    pub perceptual: u64,
+}
+
+/// Greyscale copy of the image, transparent pixels are darkened so the shape of decals counts
+fn greyscale(image: &RgbaImage, width: u32, height: u32) -> GrayImage {
+    let small = imageops::resize(image, width, height, imageops::FilterType::Triangle);
+    GrayImage::from_fn(width, height, |x, y| {
+        let [r, g, b, a] = small.get_pixel(x, y).0;
+        let luma = 0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32;
+        Luma([(luma * a as f32 / 255.0) as u8])
+    })
+}
+
+fn bits(values: impl Iterator<Item = bool>) -> u64 {
+    values.fold(0, |hash, bit| hash << 1 | bit as u64)
+}
+
+fn average_hash(image: &RgbaImage) -> u64 {
+    let grey = greyscale(image, HASH_SIZE as u32, HASH_SIZE as u32);
+    let mean = grey.pixels().map(|pixel| pixel.0[0] as u32).sum::<u32>() / (HASH_SIZE * HASH_SIZE) as u32;
+    bits(grey.pixels().map(|pixel| pixel.0[0] as u32 > mean))
+}
+
+fn difference_hash(image: &RgbaImage) -> u64 {
+    let grey = greyscale(image, HASH_SIZE as u32 + 1, HASH_SIZE as u32);
+    bits((0..HASH_SIZE as u32).flat_map(|y| {
+        let grey = &grey;
+        (0..HASH_SIZE as u32).map(move |x| grey.get_pixel(x, y).0[0] > grey.get_pixel(x + 1, y).0[0])
+    }))
+}
+
+/// One dimensional DCT-II of every row of a square grid
+fn dct_rows(input: &[f32]) -> Vec<f32> {
+    let mut output = vec![0.0; input.len()];
+    for (row, output) in input.chunks(DCT_SIZE).zip(output.chunks_mut(DCT_SIZE)) {
+        for (k, output) in output.iter_mut().enumerate() {
+            *output = row.iter().enumerate()
+                .map(|(n, value)| value * (std::f32::consts::PI / DCT_SIZE as f32 * (n as f32 + 0.5) * k as f32).cos())
+                .sum();
+        }
+    }
+    output
+}
+
+fn transpose(input: &[f32]) -> Vec<f32> {
+    (0..input.len()).map(|i| input[(i % DCT_SIZE) * DCT_SIZE + i / DCT_SIZE]).collect()
+}
+
+fn perceptual_hash(image: &RgbaImage) -> u64 {
+    let grey = greyscale(image, DCT_SIZE as u32, DCT_SIZE as u32);
+    let pixels: Vec<f32> = grey.pixels().map(|pixel| pixel.0[0] as f32).collect();
+    // The 2D DCT is the 1D DCT of the rows, then of the columns
+    let dct = transpose(&dct_rows(&transpose(&dct_rows(&pixels))));
+    let low: Vec<f32> = (0..HASH_SIZE).flat_map(|y| dct[y * DCT_SIZE..y * DCT_SIZE + HASH_SIZE].to_vec()).collect();
+
+    // The first value is the average brightness, it would outweigh the rest
+    let mut sorted = low[1..].to_vec();
+    sorted.sort_by(f32::total_cmp);
+    let median = sorted[sorted.len() / 2];
+    bits(low.iter().map(|value| *value > median))
+}
+
+impl ImageHash {
+    pub fn new(image: &RgbaImage) -> ImageHash {
+        ImageHash {
+            average: average_hash(image),
+            difference: difference_hash(image),
+            perceptual: perceptual_hash(image),
+        }
+    }
+
+    /// Average amount of bits that differ between the three hashes, 0 means they look the same
+    pub fn distance(&self, other: &ImageHash) -> u32 {
+        ((self.average ^ other.average).count_ones()
+            + (self.difference ^ other.difference).count_ones()
+            + (self.perceptual ^ other.perceptual).count_ones()) / 3
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct IndexEntry {
+    size: u64,
+    /// Seconds since the Unix epoch
+    modified: u64,
+    hash: ImageHash,
+}
+
+/// Hashes of images that were already read, so they only have to be read again once they change
+#[derive(Default, Serialize, Deserialize)]
+pub struct HashIndex {
+    entries: HashMap<String, IndexEntry>,
+}
+
+impl HashIndex {
+    /// Read the index from a file, an empty index is used if it doesn't exist yet
+    pub fn load(path: &Path) -> HashIndex {
+        match fs::read(path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_default(),
+            Err(_) => HashIndex::default(),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        fs::write(path, serde_json::to_vec(self)?)
+    }
+
+    /// Hash of an image, as long as its size and modification time are unchanged
+    pub fn get(&self, name: &str, size: u64, modified: u64) -> Option<ImageHash> {
+        self.entries.get(name)
+            .filter(|entry| entry.size == size && entry.modified == modified)
+            .map(|entry| entry.hash)
+    }
+
+    pub fn insert(&mut self, name: &str, size: u64, modified: u64, hash: ImageHash) {
+        self.entries.insert(name.to_owned(), IndexEntry { size, modified, hash });
+    }
+
+    /// Forget images that are no longer cached
+    pub fn retain(&mut self, keep: impl Fn(&str) -> bool) {
+        self.entries.retain(|name, _| keep(name));
+    }
+}
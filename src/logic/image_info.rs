@@ -0,0 +1,178 @@
+// Reads the size and pixel format of images from their headers, without decoding them
+//
+// PNG:
+//   The IHDR chunk always comes first, it holds the size, bit depth and colour type.
+//   Images without an alpha channel can still be transparent through a tRNS chunk,
+//   which has to come before the image data.
+// WebP:
+//   A RIFF file made of chunks. "VP8 " is lossy and "VP8L" lossless, "VP8X" comes first
+//   when the image has alpha, an animation or metadata and holds the size of the canvas.
+// KTX and KTX2:
+//   The size and format are in the fixed size header, see ktx.rs and ktx2.rs.
+use serde::{Deserialize, Serialize};
+
+use super::texture::TextureFormat;
+
+// Data format descriptor colour models and channels, see ktx2.rs
+const DFD_MODEL_ETC1S: u8 = 163;
+const DFD_MODEL_UASTC: u8 = 166;
+const DFD_CHANNEL_ETC1S_AAA: u8 = 15;
+const DFD_CHANNEL_UASTC_RGBA: u8 = 3;
+const DFD_CHANNEL_UASTC_RRRG: u8 = 5;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImageInfo {
+    pub width: u32,
+    pub height: u32,
+    /// Whether the image can have transparent pixels
+    pub alpha: bool,
+    /// Pixel format, such as "RGBA8" or "BC3"
+    pub format: String,
+}
+
+fn u16_le(bytes: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(bytes.get(offset..offset + 2)?.try_into().ok()?))
+}
+
+fn u32_le(bytes: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(bytes.get(offset..offset + 4)?.try_into().ok()?))
+}
+
+fn u32_be(bytes: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(bytes.get(offset..offset + 4)?.try_into().ok()?))
+}
+
+fn u24_le(bytes: &[u8], offset: usize) -> Option<u32> {
+    let field = bytes.get(offset..offset + 3)?;
+    Some(field[0] as u32 | (field[1] as u32) << 8 | (field[2] as u32) << 16)
+}
+
+fn probe_png(bytes: &[u8]) -> Option<ImageInfo> {
+    if bytes.get(12..16)? != b"IHDR" {
+        return None
+    }
+    let width = u32_be(bytes, 16)?;
+    let height = u32_be(bytes, 20)?;
+    let bit_depth = *bytes.get(24)?;
+    let (channels, mut alpha) = match bytes.get(25)? {
+        0 => ("L", false),
+        2 => ("RGB", false),
+        3 => ("Indexed", false),
+        4 => ("LA", true),
+        6 => ("RGBA", true),
+        _ => return None,
+    };
+
+    // Look for a tRNS chunk, only the chunks that were read can be checked
+    let mut offset = 33;
+    while let (Some(len), Some(kind)) = (u32_be(bytes, offset), bytes.get(offset + 4..offset + 8)) {
+        match kind {
+            b"tRNS" => alpha = true,
+            b"IDAT" | b"IEND" => break,
+            _ => (),
+        }
+        offset += 12 + len as usize;
+    }
+    Some(ImageInfo { width, height, alpha, format: format!("{}{}", channels, bit_depth) })
+}
+
+fn probe_webp(bytes: &[u8]) -> Option<ImageInfo> {
+    let mut canvas = None;
+    let mut offset = 12;
+    while let (Some(kind), Some(len)) = (bytes.get(offset..offset + 4), u32_le(bytes, offset + 4)) {
+        let data = offset + 8;
+        match kind {
+            b"VP8X" => {
+                let flags = *bytes.get(data)?;
+                canvas = Some((u24_le(bytes, data + 4)? + 1, u24_le(bytes, data + 7)? + 1, flags & 0x10 != 0));
+            }
+            b"VP8 " => {
+                // Frame tag, then the start code 9D 01 2A and 14 bit dimensions
+                if bytes.get(data + 3..data + 6)? != [0x9D, 0x01, 0x2A] {
+                    return None
+                }
+                let (width, height, alpha) = canvas.unwrap_or((
+                    (u16_le(bytes, data + 6)? & 0x3FFF) as u32,
+                    (u16_le(bytes, data + 8)? & 0x3FFF) as u32,
+                    false,
+                ));
+                return Some(ImageInfo { width, height, alpha, format: "VP8".to_owned() })
+            }
+            b"VP8L" => {
+                if *bytes.get(data)? != 0x2F {
+                    return None
+                }
+                let bits = u32_le(bytes, data + 1)?;
+                let (width, height, alpha) = canvas.unwrap_or((
+                    (bits & 0x3FFF) + 1,
+                    (bits >> 14 & 0x3FFF) + 1,
+                    bits >> 28 & 1 != 0,
+                ));
+                return Some(ImageInfo { width, height, alpha, format: "VP8L".to_owned() })
+            }
+            // Frames of an animation hold their own image chunks after a 16 byte header
+            b"ANMF" => {
+                offset = data + 16;
+                continue
+            }
+            _ => (),
+        }
+        offset = data + (len as usize).next_multiple_of(2);
+    }
+    // The image chunk wasn't read, the canvas still has the size
+    let (width, height, alpha) = canvas?;
+    Some(ImageInfo { width, height, alpha, format: "VP8X".to_owned() })
+}
+
+fn texture_info(format: TextureFormat, width: u32, height: u32) -> ImageInfo {
+    ImageInfo { width, height: height.max(1), alpha: format.has_alpha(), format: format.name() }
+}
+
+fn probe_ktx(bytes: &[u8]) -> Option<ImageInfo> {
+    let big_endian = match bytes.get(12..16)? {
+        [0x01, 0x02, 0x03, 0x04] => false,
+        [0x04, 0x03, 0x02, 0x01] => true,
+        _ => return None,
+    };
+    let read_u32 = |offset: usize| if big_endian { u32_be(bytes, offset) } else { u32_le(bytes, offset) };
+    let format = TextureFormat::from_gl(read_u32(16)?, read_u32(24)?, read_u32(28)?)?;
+    Some(texture_info(format, read_u32(36)?, read_u32(40)?))
+}
+
+fn probe_ktx2(bytes: &[u8]) -> Option<ImageInfo> {
+    let vk_format = u32_le(bytes, 12)?;
+    let width = u32_le(bytes, 20)?;
+    let height = u32_le(bytes, 24)?;
+    if let Some(format) = TextureFormat::from_vk(vk_format) {
+        return Some(texture_info(format, width, height))
+    }
+
+    // Basis Universal textures describe their channels in the data format descriptor,
+    // each sample is 16 bytes with the channel in the low bits of its fourth byte
+    let dfd = u32_le(bytes, 48)? as usize;
+    let block_size = u16_le(bytes, dfd + 10)? as usize;
+    let channels: Vec<u8> = (0..block_size.saturating_sub(24) / 16)
+        .filter_map(|sample| bytes.get(dfd + 28 + sample * 16 + 3).map(|channel| channel & 0xF))
+        .collect();
+    let (format, alpha) = match *bytes.get(dfd + 12)? {
+        DFD_MODEL_ETC1S => ("ETC1S", channels.contains(&DFD_CHANNEL_ETC1S_AAA)),
+        DFD_MODEL_UASTC => ("UASTC", channels.iter().any(|channel| matches!(*channel, DFD_CHANNEL_UASTC_RGBA | DFD_CHANNEL_UASTC_RRRG))),
+        _ => return None,
+    };
+    Some(ImageInfo { width, height: height.max(1), alpha, format: format.to_owned() })
+}
+
+/// Read the size and format of a PNG, WebP, KTX or KTX2 image from the start of it
+pub fn probe(bytes: &[u8]) -> Option<ImageInfo> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1A\n") {
+        probe_png(bytes)
+    } else if bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(b"WEBP") {
+        probe_webp(bytes)
+    } else if super::ktx::is_ktx(bytes) {
+        probe_ktx(bytes)
+    } else if super::ktx2::is_ktx2(bytes) {
+        probe_ktx2(bytes)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,127 @@
+// Parser for KTX (version 1) texture containers
+//
+// Layout:
+//   [u8; 12]       identifier "«KTX 11»\r\n\x1A\n"
+//   u32            endianness, 0x04030201 in the file's byte order
+//   u32 * 12       glType, glTypeSize, glFormat, glInternalFormat, glBaseInternalFormat,
+//                  pixelWidth, pixelHeight, pixelDepth, numberOfArrayElements,
+//                  numberOfFaces, numberOfMipmapLevels, bytesOfKeyValueData
+//   [u8]           key/value data
+//   for each mip level:
+//     u32          imageSize
+//     [u8]         image data for every array element and face, padded to 4 bytes
+use std::io::{Error, ErrorKind, Result};
+
+use image::RgbaImage;
+
+use super::texture::{self, TextureFormat};
+
+const IDENTIFIER: [u8; 12] = [0xAB, 0x4B, 0x54, 0x58, 0x20, 0x31, 0x31, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A];
+const HEADER_LEN: usize = 64;
+
+#[derive(Debug, Clone)]
+pub struct KtxTexture {
+    pub format: TextureFormat,
+    pub width: u32,
+    pub height: u32,
+    /// Image data of the first face and array element of every mip level, largest first
+    pub levels: Vec<Vec<u8>>,
+}
+
+fn invalid(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
+
+pub fn is_ktx(bytes: &[u8]) -> bool {
+    bytes.starts_with(&IDENTIFIER)
+}
+
+fn align4(value: usize) -> usize {
+    value.next_multiple_of(4)
+}
+
+impl KtxTexture {
+    pub fn parse(bytes: &[u8]) -> Result<KtxTexture> {
+        if !is_ktx(bytes) || bytes.len() < HEADER_LEN {
+            return Err(invalid("Not a KTX file"));
+        }
+
+        let big_endian = match &bytes[12..16] {
+            [0x01, 0x02, 0x03, 0x04] => false,
+            [0x04, 0x03, 0x02, 0x01] => true,
+            _ => return Err(invalid("Invalid KTX endianness")),
+        };
+        let read_u32 = |offset: usize| -> Result<u32> {
+            let field: [u8; 4] = offset.checked_add(4).and_then(|end| bytes.get(offset..end))
+                .ok_or_else(|| invalid("KTX file is truncated"))?
+                .try_into().unwrap();
+            Ok(if big_endian { u32::from_be_bytes(field) } else { u32::from_le_bytes(field) })
+        };
+
+        let gl_type = read_u32(16)?;
+        let gl_format = read_u32(24)?;
+        let gl_internal_format = read_u32(28)?;
+        let width = read_u32(36)?;
+        let height = read_u32(40)?.max(1); // 1D textures have a height of 0
+        let array_elements = read_u32(48)?;
+        let faces = read_u32(52)?.max(1);
+        // 0 means the mipmaps should be generated, and there are no levels after the 1x1 one
+        let mip_levels = read_u32(56)?.clamp(1, width.max(height).max(1).ilog2() + 1);
+        let key_value_len = read_u32(60)? as usize;
+
+        if width > texture::MAX_DIMENSION || height > texture::MAX_DIMENSION {
+            return Err(Error::new(ErrorKind::Unsupported, format!("KTX texture is too large ({}x{})", width, height)));
+        }
+        let format = TextureFormat::from_gl(gl_type, gl_format, gl_internal_format)
+            .ok_or_else(|| Error::new(ErrorKind::Unsupported, format!("Unsupported KTX format 0x{:X}", gl_internal_format)))?;
+
+        let truncated = || invalid("KTX file is truncated");
+        let mut offset = HEADER_LEN.checked_add(key_value_len).ok_or_else(truncated)?;
+        let mut levels = Vec::new();
+        for level in 0..mip_levels {
+            let image_size = read_u32(offset)? as usize;
+            offset += 4;
+
+            let (level_width, level_height) = (width >> level, height >> level);
+            let face_size = format.image_size(level_width.max(1), level_height.max(1), true).ok_or_else(truncated)?;
+            let data = offset.checked_add(face_size.min(image_size))
+                .and_then(|end| bytes.get(offset..end))
+                .ok_or_else(truncated)?;
+            levels.push(data.to_vec());
+
+            // imageSize is for a single face of non-array cubemaps, each face is padded
+            let level_size = if faces == 6 && array_elements == 0 {
+                align4(image_size).checked_mul(6)
+            } else {
+                Some(align4(image_size))
+            };
+            offset = level_size.and_then(|size| offset.checked_add(size)).ok_or_else(truncated)?;
+        }
+
+        Ok(KtxTexture { format, width, height, levels })
+    }
+
+    pub fn level_dimensions(&self, level: usize) -> (u32, u32) {
+        let level = level.min(31); // Every level from the 32nd on is 1x1
+        ((self.width >> level).max(1), (self.height >> level).max(1))
+    }
+
+    /// Smallest mip level that is at least the size given, used for previews
+    pub fn preview_level(&self, size: u32) -> usize {
+        let mut chosen = 0;
+        for level in 0..self.levels.len() {
+            let (width, height) = self.level_dimensions(level);
+            if width.max(height) >= size {
+                chosen = level;
+            }
+        }
+        chosen
+    }
+
+    pub fn decode_level(&self, level: usize) -> Result<RgbaImage> {
+        let data = self.levels.get(level)
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, format!("KTX file has no mip level {}", level)))?;
+        let (width, height) = self.level_dimensions(level);
+        texture::decode(self.format, data, width, height, true)
+    }
+}
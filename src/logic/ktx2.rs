@@ -0,0 +1,191 @@
+// Parser for KTX2 texture containers
+//
+// Layout (all integers are little endian):
+//   [u8; 12]       identifier "«KTX 20»\r\n\x1A\n"
+//   u32 * 9        vkFormat, typeSize, pixelWidth, pixelHeight, pixelDepth,
+//                  layerCount, faceCount, levelCount, supercompressionScheme
+//   u32 * 4        dfdByteOffset, dfdByteLength, kvdByteOffset, kvdByteLength
+//   u64 * 2        sgdByteOffset, sgdByteLength
+//   for each mip level, largest first:
+//     u64 * 3      byteOffset, byteLength, uncompressedByteLength
+//
+// Levels may be supercompressed on their own (zstd, zlib) or share the BasisLZ codebooks
+// in the supercompression global data
+use std::io::{Error, ErrorKind, Result};
+
+use image::RgbaImage;
+
+use super::basis;
+use super::decompress::{self, Encoding, Limit};
+use super::texture::{self, TextureFormat};
+
+const IDENTIFIER: [u8; 12] = [0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A];
+const HEADER_LEN: usize = 80;
+const LEVEL_INDEX_LEN: usize = 24;
+// Dimensions are u32, so every level after the 32nd is 1x1
+const MAX_LEVELS: usize = 32;
+// Largest level a supercompressed level may decompress to
+const MAX_LEVEL_LEN: u64 = 256 * 1024 * 1024;
+
+// Data format descriptor colour models used when vkFormat is undefined
+const DFD_MODEL_ETC1S: u8 = 163;
+const DFD_MODEL_UASTC: u8 = 166;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Supercompression {
+    None,
+    BasisLz,
+    Zstd,
+    Zlib,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Ktx2Format {
+    Texture(TextureFormat),
+    /// ETC1S, always stored with BasisLZ
+    Etc1s,
+    Uastc,
+}
+
+#[derive(Debug, Clone)]
+pub struct Ktx2Texture {
+    pub format: Ktx2Format,
+    pub width: u32,
+    pub height: u32,
+    pub supercompression: Supercompression,
+    /// Images per level, every layer and face of a level is stored together
+    images_per_level: usize,
+    /// Level data as stored in the file, largest first
+    pub levels: Vec<Vec<u8>>,
+    /// Size of each level once the supercompression is undone
+    uncompressed_lengths: Vec<usize>,
+    global_data: Vec<u8>,
+}
+
+fn invalid(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
+
+pub fn is_ktx2(bytes: &[u8]) -> bool {
+    bytes.starts_with(&IDENTIFIER)
+}
+
+fn slice(bytes: &[u8], offset: u64, len: u64) -> Result<&[u8]> {
+    let start = usize::try_from(offset).map_err(|_| invalid("KTX2 offset is too large"))?;
+    let end = usize::try_from(offset.saturating_add(len)).map_err(|_| invalid("KTX2 offset is too large"))?;
+    bytes.get(start..end).ok_or_else(|| invalid("KTX2 file is truncated"))
+}
+
+impl Ktx2Texture {
+    pub fn parse(bytes: &[u8]) -> Result<Ktx2Texture> {
+        if !is_ktx2(bytes) || bytes.len() < HEADER_LEN {
+            return Err(invalid("Not a KTX2 file"));
+        }
+        let read_u32 = |offset: usize| -> Result<u32> {
+            let field = bytes.get(offset..offset + 4).ok_or_else(|| invalid("KTX2 file is truncated"))?;
+            Ok(u32::from_le_bytes(field.try_into().unwrap()))
+        };
+        let read_u64 = |offset: usize| -> Result<u64> {
+            let field = bytes.get(offset..offset + 8).ok_or_else(|| invalid("KTX2 file is truncated"))?;
+            Ok(u64::from_le_bytes(field.try_into().unwrap()))
+        };
+
+        let vk_format = read_u32(12)?;
+        let width = read_u32(20)?;
+        let height = read_u32(24)?.max(1); // 1D textures have a height of 0
+        let layers = read_u32(32)?.max(1) as usize;
+        let faces = read_u32(36)?.max(1) as usize;
+        let level_count = (read_u32(40)?.max(1) as usize).min(MAX_LEVELS); // 0 means the mipmaps should be generated
+        if width > texture::MAX_DIMENSION || height > texture::MAX_DIMENSION {
+            return Err(Error::new(ErrorKind::Unsupported, format!("KTX2 texture is too large ({}x{})", width, height)));
+        }
+        let supercompression = match read_u32(44)? {
+            0 => Supercompression::None,
+            1 => Supercompression::BasisLz,
+            2 => Supercompression::Zstd,
+            3 => Supercompression::Zlib,
+            scheme => return Err(Error::new(ErrorKind::Unsupported, format!("Unsupported KTX2 supercompression scheme {}", scheme))),
+        };
+        let dfd_offset = read_u32(48)? as usize;
+        let global_data = slice(bytes, read_u64(64)?, read_u64(72)?)?.to_vec();
+
+        let format = match TextureFormat::from_vk(vk_format) {
+            Some(format) => Ktx2Format::Texture(format),
+            // Undefined formats are described by the colour model in the data format descriptor
+            None if vk_format == 0 => match bytes.get(dfd_offset + 12) {
+                Some(&DFD_MODEL_ETC1S) => Ktx2Format::Etc1s,
+                Some(&DFD_MODEL_UASTC) => Ktx2Format::Uastc,
+                _ => return Err(Error::new(ErrorKind::Unsupported, "Unsupported KTX2 colour model")),
+            },
+            None => return Err(Error::new(ErrorKind::Unsupported, format!("Unsupported KTX2 format {}", vk_format))),
+        };
+        if (format == Ktx2Format::Etc1s) != (supercompression == Supercompression::BasisLz) {
+            return Err(invalid("BasisLZ is only used for ETC1S textures"));
+        }
+
+        let level_index_end = level_count.checked_mul(LEVEL_INDEX_LEN).and_then(|len| len.checked_add(HEADER_LEN));
+        if level_index_end.is_none_or(|end| end > bytes.len()) {
+            return Err(invalid("KTX2 file is truncated"));
+        }
+        let mut levels = Vec::with_capacity(level_count);
+        let mut uncompressed_lengths = Vec::with_capacity(level_count);
+        for level in 0..level_count {
+            let index = HEADER_LEN + level * LEVEL_INDEX_LEN;
+            levels.push(slice(bytes, read_u64(index)?, read_u64(index + 8)?)?.to_vec());
+            uncompressed_lengths.push(read_u64(index + 16)?.min(MAX_LEVEL_LEN) as usize);
+        }
+
+        Ok(Ktx2Texture {
+            format,
+            width,
+            height,
+            supercompression,
+            images_per_level: layers * faces,
+            levels,
+            uncompressed_lengths,
+            global_data,
+        })
+    }
+
+    pub fn level_dimensions(&self, level: usize) -> (u32, u32) {
+        let level = level.min(MAX_LEVELS - 1);
+        ((self.width >> level).max(1), (self.height >> level).max(1))
+    }
+
+    /// Smallest mip level that is at least the size given, used for previews
+    pub fn preview_level(&self, size: u32) -> usize {
+        let mut chosen = 0;
+        for level in 0..self.levels.len() {
+            let (width, height) = self.level_dimensions(level);
+            if width.max(height) >= size {
+                chosen = level;
+            }
+        }
+        chosen
+    }
+
+    /// Undo the supercompression of a level, BasisLZ levels are decoded with the global codebooks instead
+    pub fn level_data(&self, level: usize) -> Result<Vec<u8>> {
+        let data = self.levels.get(level)
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, format!("KTX2 file has no mip level {}", level)))?;
+        match self.supercompression {
+            Supercompression::Zstd => decompress::decode(data, &[Encoding::Zstd], Limit::Max(self.uncompressed_lengths[level])),
+            Supercompression::Zlib => decompress::decode(data, &[Encoding::Deflate], Limit::Max(self.uncompressed_lengths[level])),
+            Supercompression::None | Supercompression::BasisLz => Ok(data.clone()),
+        }
+    }
+
+    /// Decode the first layer and face of a mip level
+    pub fn decode_level(&self, level: usize) -> Result<RgbaImage> {
+        let data = self.level_data(level)?;
+        let (width, height) = self.level_dimensions(level);
+        match self.format {
+            Ktx2Format::Texture(format) => texture::decode(format, &data, width, height, false),
+            Ktx2Format::Etc1s => {
+                let codebook = basis::Codebook::parse(&self.global_data, self.levels.len() * self.images_per_level)?;
+                codebook.decode_image(level * self.images_per_level, &data, width, height)
+            }
+            Ktx2Format::Uastc => Err(Error::new(ErrorKind::Unsupported, "UASTC textures are not supported")),
+        }
+    }
+}
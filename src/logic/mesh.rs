@@ -0,0 +1,289 @@
+// Parser for mesh files, versions 1.00 to 5.00
+//
+// Every version starts with a text line such as "version 4.00". Version 1 is text after that,
+// the others are binary (all integers are little endian):
+//   2.00   u16 header size, u8 vertex size, u8 face size, u32 vertex count, u32 face count
+//   3.0x   u16 header size, u8 vertex size, u8 face size, u16 LOD size, u16 LOD count,
+//          u32 vertex count, u32 face count
+//   4.0x   u16 header size, u16 LOD type, u32 vertex count, u32 face count, u16 LOD count,
+//          u16 bone count, u32 bone name size, u16 subset count, u8 high quality LODs, u8 unused
+//   5.00   the same as 4.0x, followed by u32 FACS format and u32 FACS size
+// After the header come the vertices, the skinning of each vertex (4.00+ with bones), the faces,
+// the face offsets of each LOD (3.00+), the bones, their names and the skinning subsets.
+use std::io::{Error, ErrorKind, Result};
+use std::ops::Range;
+
+const VERTEX_LEN: usize = 36; // Vertices can be 40 bytes with a colour
+const ENVELOPE_LEN: usize = 8;
+const FACE_LEN: usize = 12;
+const BONE_LEN: usize = 60;
+const SUBSET_LEN: usize = 72;
+const SUBSET_BONES: usize = 26;
+
+#[derive(Debug, Clone)]
+pub struct Vertex {
+    pub position: [f32; 3],
+    pub normal: [f32; 3],
+    /// Origin at the top left, like glTF
+    pub uv: [f32; 2],
+    pub color: [u8; 4],
+}
+
+#[derive(Debug, Clone)]
+pub struct Bone {
+    pub name: String,
+    pub parent: Option<usize>,
+    /// Rotation matrix row by row, in model space
+    pub rotation: [f32; 9],
+    pub position: [f32; 3],
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Mesh {
+    pub version: String,
+    pub vertices: Vec<Vertex>,
+    pub faces: Vec<[u32; 3]>,
+    /// Faces of each level of detail, the first one is the full quality mesh
+    pub lods: Vec<Range<usize>>,
+    pub bones: Vec<Bone>,
+    /// Up to 4 bones and their weights (out of 255) for each vertex, empty if the mesh has no bones
+    pub skinning: Vec<[(u16, u8); 4]>,
+}
+
+fn invalid(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
+
+pub fn is_mesh(bytes: &[u8]) -> bool {
+    bytes.starts_with(b"version ")
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self.position.checked_add(len).filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| invalid("Mesh file is truncated"))?;
+        let slice = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn f32s<const N: usize>(&mut self) -> Result<[f32; N]> {
+        let bytes = self.take(N * 4)?;
+        Ok(std::array::from_fn(|i| f32::from_le_bytes(bytes[i * 4..i * 4 + 4].try_into().unwrap())))
+    }
+
+    /// Take count items of a size, checking the whole length first so huge counts fail early
+    fn items(&mut self, count: usize, size: usize) -> Result<Reader<'a>> {
+        let bytes = self.take(count.checked_mul(size).ok_or_else(|| invalid("Mesh file is truncated"))?)?;
+        Ok(Reader { bytes, position: 0 })
+    }
+}
+
+fn read_vertices(reader: &mut Reader, count: usize, size: usize) -> Result<Vec<Vertex>> {
+    if size < VERTEX_LEN {
+        return Err(invalid("Mesh vertices are too small"));
+    }
+    let mut items = reader.items(count, size)?;
+    (0..count).map(|_| {
+        let mut vertex = Reader { bytes: items.take(size)?, position: 0 };
+        let position = vertex.f32s()?;
+        let normal = vertex.f32s()?;
+        let uv = vertex.f32s()?;
+        vertex.take(4)?; // Tangent
+        let color = if size >= VERTEX_LEN + 4 { vertex.take(4)?.try_into().unwrap() } else { [255; 4] };
+        Ok(Vertex { position, normal, uv, color })
+    }).collect()
+}
+
+fn read_faces(reader: &mut Reader, count: usize, size: usize, vertex_count: usize) -> Result<Vec<[u32; 3]>> {
+    if size < FACE_LEN {
+        return Err(invalid("Mesh faces are too small"));
+    }
+    let mut items = reader.items(count, size)?;
+    (0..count).map(|_| {
+        let mut face = Reader { bytes: items.take(size)?, position: 0 };
+        let indices = [face.u32()?, face.u32()?, face.u32()?];
+        if indices.iter().any(|i| *i as usize >= vertex_count) {
+            return Err(invalid("Mesh face uses a vertex that doesn't exist"));
+        }
+        Ok(indices)
+    }).collect()
+}
+
+/// LODs are stored as the face each one starts at, with the end of the last one at the end
+fn lod_ranges(offsets: &[u32], face_count: usize) -> Vec<Range<usize>> {
+    let ranges: Vec<Range<usize>> = offsets.windows(2)
+        .map(|pair| (pair[0] as usize).min(face_count)..(pair[1] as usize).min(face_count))
+        .filter(|range| !range.is_empty())
+        .collect();
+    if ranges.is_empty() {
+        std::iter::once(0..face_count).collect()
+    } else {
+        ranges
+    }
+}
+
+fn parse_text(text: &str, version: &str) -> Result<Mesh> {
+    let mut lines = text.lines().skip(1);
+    let face_count: usize = lines.next().and_then(|line| line.trim().parse().ok())
+        .ok_or_else(|| invalid("Mesh face count is missing"))?;
+    let data: String = lines.collect::<Vec<_>>().join(" ");
+    let numbers = data.split(['[', ']', ',', ' ', '\t'])
+        .filter(|number| !number.is_empty())
+        .map(|number| number.parse::<f32>().map_err(|_| invalid("Invalid number in mesh")))
+        .collect::<Result<Vec<f32>>>()?;
+
+    // Each vertex is [position][normal][uv], three vertices to a face
+    let vertex_count = face_count.checked_mul(3).ok_or_else(|| invalid("Mesh file is truncated"))?;
+    if numbers.len() < vertex_count.saturating_mul(9) {
+        return Err(invalid("Mesh file is truncated"));
+    }
+    let scale = if version == "1.00" { 0.5 } else { 1.0 }; // 1.00 meshes are twice as big
+    let vertices = numbers.chunks_exact(9).take(vertex_count).map(|v| Vertex {
+        position: [v[0] * scale, v[1] * scale, v[2] * scale],
+        normal: [v[3], v[4], v[5]],
+        uv: [v[6], 1.0 - v[7]], // Version 1 has the origin at the bottom left
+        color: [255; 4],
+    }).collect();
+
+    Ok(Mesh {
+        version: version.to_owned(),
+        vertices,
+        faces: (0..face_count as u32).map(|face| [face * 3, face * 3 + 1, face * 3 + 2]).collect(),
+        lods: std::iter::once(0..face_count).collect(),
+        ..Default::default()
+    })
+}
+
+impl Mesh {
+    pub fn parse(bytes: &[u8]) -> Result<Mesh> {
+        if !is_mesh(bytes) {
+            return Err(invalid("Not a mesh file"));
+        }
+        let line_end = bytes.iter().position(|b| *b == b'\n').ok_or_else(|| invalid("Mesh file is truncated"))?;
+        let version = String::from_utf8_lossy(&bytes[8..line_end]).trim().to_owned();
+        let major = version.split('.').next().unwrap_or("");
+
+        if major == "1" {
+            return parse_text(&String::from_utf8_lossy(bytes), &version);
+        }
+
+        let mut reader = Reader { bytes, position: line_end + 1 };
+        let header_start = reader.position;
+        let header_size = reader.u16()? as usize;
+        let mut mesh = Mesh { version: version.clone(), ..Default::default() };
+
+        match major {
+            "2" | "3" => {
+                let vertex_size = reader.u8()? as usize;
+                let face_size = reader.u8()? as usize;
+                let (lod_size, lod_count) = if major == "3" { (reader.u16()? as usize, reader.u16()? as usize) } else { (4, 0) };
+                let vertex_count = reader.u32()? as usize;
+                let face_count = reader.u32()? as usize;
+                reader.position = header_start + header_size;
+
+                mesh.vertices = read_vertices(&mut reader, vertex_count, vertex_size)?;
+                mesh.faces = read_faces(&mut reader, face_count, face_size, vertex_count)?;
+                let mut lods = reader.items(lod_count, lod_size.max(4))?;
+                let offsets = (0..lod_count).map(|_| {
+                    let offset = lods.u32()?;
+                    lods.take(lod_size.saturating_sub(4))?;
+                    Ok(offset)
+                }).collect::<Result<Vec<u32>>>()?;
+                mesh.lods = lod_ranges(&offsets, face_count);
+            }
+            "4" | "5" => {
+                let _lod_type = reader.u16()?;
+                let vertex_count = reader.u32()? as usize;
+                let face_count = reader.u32()? as usize;
+                let lod_count = reader.u16()? as usize;
+                let bone_count = reader.u16()? as usize;
+                let bone_names_size = reader.u32()? as usize;
+                let subset_count = reader.u16()? as usize;
+                reader.position = header_start + header_size;
+
+                mesh.vertices = read_vertices(&mut reader, vertex_count, VERTEX_LEN + 4)?;
+                let mut envelopes = reader.items(if bone_count > 0 { vertex_count } else { 0 }, ENVELOPE_LEN)?;
+                let envelopes = (0..envelopes.bytes.len() / ENVELOPE_LEN)
+                    .map(|_| envelopes.take(ENVELOPE_LEN).map(|e| [(e[0], e[4]), (e[1], e[5]), (e[2], e[6]), (e[3], e[7])]))
+                    .collect::<Result<Vec<_>>>()?;
+                mesh.faces = read_faces(&mut reader, face_count, FACE_LEN, vertex_count)?;
+                let mut lods = reader.items(lod_count, 4)?;
+                let offsets = (0..lod_count).map(|_| lods.u32()).collect::<Result<Vec<u32>>>()?;
+                mesh.lods = lod_ranges(&offsets, face_count);
+
+                let mut bones = reader.items(bone_count, BONE_LEN)?;
+                let mut name_offsets = Vec::with_capacity(bone_count);
+                for _ in 0..bone_count {
+                    name_offsets.push(bones.u32()? as usize);
+                    let parent = bones.u16()?;
+                    let _lod_parent = bones.u16()?;
+                    let _culling = bones.f32s::<1>()?;
+                    mesh.bones.push(Bone {
+                        name: String::new(),
+                        parent: (parent != 0xFFFF && (parent as usize) < bone_count).then_some(parent as usize),
+                        rotation: bones.f32s()?,
+                        position: bones.f32s()?,
+                    });
+                }
+                // Names are null terminated strings, found by their offset
+                let names = reader.take(bone_names_size)?;
+                for (bone, offset) in mesh.bones.iter_mut().zip(name_offsets) {
+                    let name = names.get(offset..).unwrap_or_default();
+                    let end = name.iter().position(|b| *b == 0).unwrap_or(name.len());
+                    bone.name = String::from_utf8_lossy(&name[..end]).to_string();
+                }
+
+                // Each subset maps the bones used by its vertices to the whole list of bones
+                if bone_count > 0 {
+                    mesh.skinning = vec![[(0, 0); 4]; vertex_count];
+                    let mut subsets = reader.items(subset_count, SUBSET_LEN)?;
+                    for _ in 0..subset_count {
+                        let _faces = (subsets.u32()?, subsets.u32()?);
+                        let vertices_begin = subsets.u32()? as usize;
+                        let vertices_len = subsets.u32()? as usize;
+                        let _bone_count = subsets.u32()?;
+                        let subset_bones = (0..SUBSET_BONES).map(|_| subsets.u16()).collect::<Result<Vec<u16>>>()?;
+
+                        let end = vertices_begin.saturating_add(vertices_len).min(vertex_count);
+                        let range = vertices_begin.min(end)..end;
+                        for (skinning, envelope) in mesh.skinning[range.clone()].iter_mut().zip(&envelopes[range]) {
+                            *skinning = envelope.map(|(bone, weight)| {
+                                match subset_bones.get(bone as usize) {
+                                    Some(bone) if weight > 0 && (*bone as usize) < bone_count => (*bone, weight),
+                                    _ => (0, 0),
+                                }
+                            });
+                        }
+                    }
+                }
+                // The FACS data of 5.00 is for facial animation, which isn't exported
+            }
+            _ => return Err(Error::new(ErrorKind::Unsupported, format!("Unsupported mesh version {}", version))),
+        }
+
+        Ok(mesh)
+    }
+
+    /// Vertex colours are only kept when they aren't all white
+    pub fn has_colors(&self) -> bool {
+        self.vertices.iter().any(|vertex| vertex.color != [255; 4])
+    }
+}
@@ -0,0 +1,223 @@
+// Writers for OBJ and glTF, used to convert meshes when extracting them
+//
+// Every level of detail is exported as its own object, so the lower quality ones can be
+// inspected too. Only glTF can hold the bones and skinning.
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use serde_json::{json, Value};
+
+use super::mesh::Mesh;
+
+// glTF constants
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+const UNSIGNED_BYTE: u32 = 5121;
+const UNSIGNED_SHORT: u32 = 5123;
+const UNSIGNED_INT: u32 = 5125;
+const FLOAT: u32 = 5126;
+
+pub fn to_obj(mesh: &Mesh) -> String {
+    let mut output = format!("# Version {} mesh exported by RoExtract\n", mesh.version);
+    for vertex in &mesh.vertices {
+        let [x, y, z] = vertex.position;
+        output.push_str(&format!("v {} {} {}\n", x, y, z));
+    }
+    for vertex in &mesh.vertices {
+        output.push_str(&format!("vt {} {}\n", vertex.uv[0], 1.0 - vertex.uv[1])); // OBJ has the origin at the bottom left
+    }
+    for vertex in &mesh.vertices {
+        let [x, y, z] = vertex.normal;
+        output.push_str(&format!("vn {} {} {}\n", x, y, z));
+    }
+    for (lod, faces) in mesh.lods.iter().enumerate() {
+        output.push_str(&format!("o LOD{}\n", lod));
+        for face in &mesh.faces[faces.clone()] {
+            let [a, b, c] = face.map(|i| i + 1); // Indices start at 1
+            output.push_str(&format!("f {0}/{0}/{0} {1}/{1}/{1} {2}/{2}/{2}\n", a, b, c));
+        }
+    }
+    output
+}
+
+/// Buffer that every accessor points into, embedded in the file
+#[derive(Default)]
+struct Buffer {
+    data: Vec<u8>,
+    views: Vec<Value>,
+    accessors: Vec<Value>,
+}
+
+impl Buffer {
+    /// Add a view with one accessor over it, returning the accessor index
+    fn add(&mut self, data: &[u8], target: Option<u32>, accessor: Value) -> usize {
+        while !self.data.len().is_multiple_of(4) {
+            self.data.push(0); // Accessors have to be aligned
+        }
+        let mut view = json!({ "buffer": 0, "byteOffset": self.data.len(), "byteLength": data.len() });
+        if let Some(target) = target {
+            view["target"] = json!(target);
+        }
+        self.data.extend_from_slice(data);
+
+        let mut accessor = accessor;
+        accessor["bufferView"] = json!(self.views.len());
+        self.views.push(view);
+        self.accessors.push(accessor);
+        self.accessors.len() - 1
+    }
+
+    fn add_floats<const N: usize>(&mut self, values: &[[f32; N]], kind: &str, target: Option<u32>, bounds: bool) -> usize {
+        let data: Vec<u8> = values.iter().flatten().flat_map(|v| v.to_le_bytes()).collect();
+        let mut accessor = json!({ "componentType": FLOAT, "count": values.len(), "type": kind });
+        if bounds {
+            // Positions need their bounds
+            accessor["min"] = json!((0..N).map(|i| values.iter().map(|v| v[i]).fold(f32::INFINITY, f32::min)).collect::<Vec<_>>());
+            accessor["max"] = json!((0..N).map(|i| values.iter().map(|v| v[i]).fold(f32::NEG_INFINITY, f32::max)).collect::<Vec<_>>());
+        }
+        self.add(&data, target, accessor)
+    }
+}
+
+fn quaternion(m: &[f32; 9]) -> [f32; 4] {
+    // Rotation matrix to x, y, z, w
+    let trace = m[0] + m[4] + m[8];
+    let q = if trace > 0.0 {
+        let s = (trace + 1.0).sqrt() * 2.0;
+        [(m[7] - m[5]) / s, (m[2] - m[6]) / s, (m[3] - m[1]) / s, 0.25 * s]
+    } else if m[0] > m[4] && m[0] > m[8] {
+        let s = (1.0 + m[0] - m[4] - m[8]).sqrt() * 2.0;
+        [0.25 * s, (m[1] + m[3]) / s, (m[2] + m[6]) / s, (m[7] - m[5]) / s]
+    } else if m[4] > m[8] {
+        let s = (1.0 + m[4] - m[0] - m[8]).sqrt() * 2.0;
+        [(m[1] + m[3]) / s, 0.25 * s, (m[5] + m[7]) / s, (m[2] - m[6]) / s]
+    } else {
+        let s = (1.0 + m[8] - m[0] - m[4]).sqrt() * 2.0;
+        [(m[2] + m[6]) / s, (m[5] + m[7]) / s, 0.25 * s, (m[3] - m[1]) / s]
+    };
+    let length = q.iter().map(|v| v * v).sum::<f32>().sqrt();
+    q.map(|v| v / length)
+}
+
+/// Transpose of a rotation matrix, which is also its inverse
+fn transpose(m: &[f32; 9]) -> [f32; 9] {
+    [m[0], m[3], m[6], m[1], m[4], m[7], m[2], m[5], m[8]]
+}
+
+fn multiply(m: &[f32; 9], v: [f32; 3]) -> [f32; 3] {
+    std::array::from_fn(|row| m[row * 3] * v[0] + m[row * 3 + 1] * v[1] + m[row * 3 + 2] * v[2])
+}
+
+fn multiply_matrix(a: &[f32; 9], b: &[f32; 9]) -> [f32; 9] {
+    std::array::from_fn(|i| (0..3).map(|k| a[i / 3 * 3 + k] * b[k * 3 + i % 3]).sum())
+}
+
+pub fn to_gltf(mesh: &Mesh) -> String {
+    let mut buffer = Buffer::default();
+    let mut nodes = Vec::new();
+    let mut scene_nodes = Vec::new();
+
+    let positions: Vec<[f32; 3]> = mesh.vertices.iter().map(|v| v.position).collect();
+    let normals: Vec<[f32; 3]> = mesh.vertices.iter().map(|v| v.normal).collect();
+    let uvs: Vec<[f32; 2]> = mesh.vertices.iter().map(|v| v.uv).collect();
+    let mut attributes = json!({
+        "POSITION": buffer.add_floats(&positions, "VEC3", Some(ARRAY_BUFFER), true),
+        "NORMAL": buffer.add_floats(&normals, "VEC3", Some(ARRAY_BUFFER), false),
+        "TEXCOORD_0": buffer.add_floats(&uvs, "VEC2", Some(ARRAY_BUFFER), false),
+    });
+    if mesh.has_colors() {
+        let colors: Vec<u8> = mesh.vertices.iter().flat_map(|v| v.color).collect();
+        attributes["COLOR_0"] = json!(buffer.add(&colors, Some(ARRAY_BUFFER), json!({
+            "componentType": UNSIGNED_BYTE, "normalized": true, "count": mesh.vertices.len(), "type": "VEC4",
+        })));
+    }
+
+    // Bones become nodes, which store their transform relative to the parent
+    let mut skin = None;
+    if !mesh.bones.is_empty() && mesh.skinning.len() == mesh.vertices.len() {
+        let joints: Vec<u8> = mesh.skinning.iter().flatten().flat_map(|(bone, _)| bone.to_le_bytes()).collect();
+        let weights: Vec<u8> = mesh.skinning.iter().flat_map(|influences| {
+            let mut weights = influences.map(|(_, weight)| weight);
+            // Weights have to add up to one, vertices without any follow the first bone
+            let total: u32 = weights.iter().map(|w| *w as u32).sum();
+            if total == 0 {
+                weights[0] = 255;
+            } else {
+                weights[0] = weights[0].saturating_add((255u32.saturating_sub(total)) as u8);
+            }
+            weights
+        }).collect();
+        attributes["JOINTS_0"] = json!(buffer.add(&joints, Some(ARRAY_BUFFER), json!({
+            "componentType": UNSIGNED_SHORT, "count": mesh.vertices.len(), "type": "VEC4",
+        })));
+        attributes["WEIGHTS_0"] = json!(buffer.add(&weights, Some(ARRAY_BUFFER), json!({
+            "componentType": UNSIGNED_BYTE, "normalized": true, "count": mesh.vertices.len(), "type": "VEC4",
+        })));
+
+        let mut inverse_binds = Vec::new();
+        for bone in &mesh.bones {
+            let (rotation, position) = match bone.parent.and_then(|parent| mesh.bones.get(parent)) {
+                Some(parent) => {
+                    let inverse = transpose(&parent.rotation);
+                    let offset = std::array::from_fn(|i| bone.position[i] - parent.position[i]);
+                    (multiply_matrix(&inverse, &bone.rotation), multiply(&inverse, offset))
+                }
+                None => (bone.rotation, bone.position),
+            };
+            let children: Vec<usize> = (0..mesh.bones.len()).filter(|i| mesh.bones[*i].parent == Some(nodes.len())).collect();
+            let mut node = json!({ "name": bone.name, "translation": position, "rotation": quaternion(&rotation) });
+            if !children.is_empty() {
+                node["children"] = json!(children);
+            }
+            if bone.parent.is_none() {
+                scene_nodes.push(nodes.len());
+            }
+            nodes.push(node);
+
+            // Column major inverse of the bone's model space transform
+            let inverse = transpose(&bone.rotation);
+            let translation = multiply(&inverse, bone.position).map(|v| -v);
+            inverse_binds.push([
+                inverse[0], inverse[3], inverse[6], 0.0,
+                inverse[1], inverse[4], inverse[7], 0.0,
+                inverse[2], inverse[5], inverse[8], 0.0,
+                translation[0], translation[1], translation[2], 1.0,
+            ]);
+        }
+        let inverse_bind_matrices = buffer.add_floats(&inverse_binds, "MAT4", None, false); // Not vertex data
+        skin = Some(json!({ "joints": (0..mesh.bones.len()).collect::<Vec<_>>(), "inverseBindMatrices": inverse_bind_matrices }));
+    }
+
+    let mut meshes = Vec::new();
+    for (lod, faces) in mesh.lods.iter().enumerate() {
+        let indices: Vec<u8> = mesh.faces[faces.clone()].iter().flatten().flat_map(|i| i.to_le_bytes()).collect();
+        let indices = buffer.add(&indices, Some(ELEMENT_ARRAY_BUFFER), json!({
+            "componentType": UNSIGNED_INT, "count": faces.len() * 3, "type": "SCALAR",
+        }));
+        meshes.push(json!({ "name": format!("LOD{}", lod), "primitives": [{ "attributes": attributes, "indices": indices }] }));
+
+        let mut node = json!({ "name": format!("LOD{}", lod), "mesh": lod });
+        if skin.is_some() {
+            node["skin"] = json!(0);
+        }
+        scene_nodes.push(nodes.len());
+        nodes.push(node);
+    }
+
+    let mut gltf = json!({
+        "asset": { "version": "2.0", "generator": "RoExtract" },
+        "scene": 0,
+        "scenes": [{ "nodes": scene_nodes }],
+        "nodes": nodes,
+        "meshes": meshes,
+        "buffers": [{
+            "byteLength": buffer.data.len(),
+            "uri": format!("data:application/octet-stream;base64,{}", BASE64.encode(&buffer.data)),
+        }],
+        "bufferViews": buffer.views,
+        "accessors": buffer.accessors,
+    });
+    if let Some(skin) = skin {
+        gltf["skins"] = json!([skin]);
+    }
+    serde_json::to_string_pretty(&gltf).unwrap_or_default()
+}
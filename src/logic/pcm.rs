@@ -0,0 +1,281 @@
+// Decodes sounds to samples for the built-in player and waveforms
+//
+// Everything here works on samples in memory, the audio device is only touched by the
+// player in the GUI, which asks a Playback to fill its buffers.
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::Duration;
+
+use image::{Rgba, RgbaImage};
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::DecoderOptions;
+use symphonia::core::errors::Error;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+/// Decoded sound, samples of every channel are interleaved
+#[derive(Debug, Clone, Default)]
+pub struct Pcm {
+    pub sample_rate: u32,
+    pub channels: usize,
+    pub samples: Vec<f32>,
+}
+
+impl Pcm {
+    pub fn decode(bytes: &[u8]) -> Result<Pcm, String> {
+        let source = MediaSourceStream::new(Box::new(Cursor::new(bytes.to_vec())), Default::default());
+        let probed = symphonia::default::get_probe()
+            .format(&Hint::new(), source, &FormatOptions::default(), &MetadataOptions::default())
+            .map_err(|e| e.to_string())?;
+        let mut format = probed.format;
+        let track = format.default_track().ok_or("No audio track")?;
+        let track_id = track.id;
+        let mut decoder = symphonia::default::get_codecs()
+            .make(&track.codec_params, &DecoderOptions::default())
+            .map_err(|e| e.to_string())?;
+
+        let mut pcm = Pcm::default();
+        let mut buffer: Option<SampleBuffer<f32>> = None;
+        loop {
+            let packet = match format.next_packet() {
+                Ok(packet) => packet,
+                Err(Error::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => break, // End of the stream
+                Err(e) => return Err(e.to_string()),
+            };
+            if packet.track_id() != track_id {
+                continue
+            }
+            let decoded = match decoder.decode(&packet) {
+                Ok(decoded) => decoded,
+                Err(Error::DecodeError(_)) => continue, // Skip over broken packets
+                Err(e) => return Err(e.to_string()),
+            };
+            let spec = *decoded.spec();
+            if buffer.as_ref().is_none_or(|buffer| buffer.capacity() < decoded.capacity()) {
+                buffer = Some(SampleBuffer::new(decoded.capacity() as u64, spec));
+            }
+            if let Some(buffer) = buffer.as_mut() {
+                buffer.copy_interleaved_ref(decoded);
+                pcm.samples.extend_from_slice(buffer.samples());
+            }
+            pcm.sample_rate = spec.rate;
+            pcm.channels = spec.channels.count();
+        }
+
+        if pcm.channels == 0 || pcm.sample_rate == 0 {
+            return Err("The sound has no samples".to_owned())
+        }
+        Ok(pcm)
+    }
+
+    /// Amount of samples in each channel
+    pub fn frames(&self) -> usize {
+        self.samples.len() / self.channels.max(1)
+    }
+
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs_f64(self.frames() as f64 / self.sample_rate.max(1) as f64)
+    }
+
+    /// Lowest and highest sample in each column, across every channel
+    pub fn waveform(&self, columns: usize) -> Vec<[f32; 2]> {
+        let frames = self.frames();
+        (0..columns).map(|column| {
+            let start = column * frames / columns.max(1);
+            let end = ((column + 1) * frames / columns.max(1)).max(start + 1).min(frames);
+            self.samples.get(start * self.channels..end * self.channels).unwrap_or_default().iter()
+                .fold([0.0f32; 2], |[low, high], sample| [low.min(*sample), high.max(*sample)])
+        }).collect()
+    }
+}
+
+const WAVEFORM_COLOR: Rgba<u8> = Rgba([150, 160, 180, 255]);
+
+impl Pcm {
+    /// Waveform drawn on a transparent background, used for thumbnails
+    pub fn waveform_image(&self, width: u32, height: u32) -> RgbaImage {
+        let mut image = RgbaImage::new(width.max(1), height.max(1));
+        let center = image.height() as f32 / 2.0;
+        for (x, [low, high]) in self.waveform(image.width() as usize).into_iter().enumerate() {
+            let top = (center - high.clamp(-1.0, 1.0) * center).floor() as u32;
+            let bottom = ((center - low.clamp(-1.0, 1.0) * center).ceil() as u32).clamp(top + 1, image.height());
+            for y in top..bottom {
+                image.put_pixel(x as u32, y, WAVEFORM_COLOR);
+            }
+        }
+        image
+    }
+}
+
+/// Position within a sound that is being played
+#[derive(Debug, Clone)]
+pub struct Playback {
+    pub pcm: Arc<Pcm>,
+    pub playing: bool,
+    /// In frames of the sound, fractional as the output can have another sample rate
+    position: f64,
+}
+
+impl Playback {
+    pub fn new(pcm: Arc<Pcm>) -> Playback {
+        Playback { pcm, playing: false, position: 0.0 }
+    }
+
+    pub fn position(&self) -> Duration {
+        Duration::from_secs_f64(self.position / self.pcm.sample_rate.max(1) as f64)
+    }
+
+    pub fn seek(&mut self, position: Duration) {
+        let frame = position.as_secs_f64() * self.pcm.sample_rate as f64;
+        self.position = frame.clamp(0.0, self.pcm.frames() as f64);
+    }
+
+    /// Start again from the beginning if the end was reached
+    pub fn play(&mut self) {
+        if self.position >= self.pcm.frames() as f64 {
+            self.position = 0.0;
+        }
+        self.playing = true;
+    }
+
+    fn sample(&self, frame: usize, channel: usize) -> f32 {
+        self.pcm.samples.get(frame * self.pcm.channels + channel).copied().unwrap_or(0.0)
+    }
+
+    /// Fill an interleaved output buffer, resampling to the output's sample rate.
+    /// Silence is written while paused, playing stops at the end of the sound.
+    #[cfg_attr(not(feature = "audio-playback"), allow(dead_code))] // Only the audio output calls this
+    pub fn fill(&mut self, output: &mut [f32], channels: usize, sample_rate: u32) {
+        let step = self.pcm.sample_rate as f64 / sample_rate.max(1) as f64;
+        let frames = self.pcm.frames();
+        for frame in output.chunks_mut(channels.max(1)) {
+            if !self.playing || self.position >= frames as f64 {
+                self.playing &= self.position < frames as f64;
+                frame.fill(0.0);
+                continue
+            }
+            // Linear interpolation between the two closest frames
+            let index = self.position as usize;
+            let fraction = (self.position - index as f64) as f32;
+            let source_channels = self.pcm.channels;
+            for (channel, output) in frame.iter_mut().enumerate() {
+                let mix = |frame: usize| if channels == 1 && source_channels > 1 {
+                    // Mix every channel down when the output is mono
+                    (0..source_channels).map(|c| self.sample(frame, c)).sum::<f32>() / source_channels as f32
+                } else {
+                    self.sample(frame, channel.min(source_channels - 1))
+                };
+                let next = (index + 1).min(frames - 1);
+                *output = mix(index) * (1.0 - fraction) + mix(next) * fraction;
+            }
+            self.position += step;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 16 bit PCM WAV file with the samples given
+    fn wav(sample_rate: u32, channels: u16, samples: &[i16]) -> Vec<u8> {
+        let data_len = samples.len() as u32 * 2;
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"RIFF");
+        bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+        bytes.extend_from_slice(b"WAVEfmt ");
+        bytes.extend_from_slice(&16u32.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&channels.to_le_bytes());
+        bytes.extend_from_slice(&sample_rate.to_le_bytes());
+        bytes.extend_from_slice(&(sample_rate * channels as u32 * 2).to_le_bytes());
+        bytes.extend_from_slice(&(channels * 2).to_le_bytes());
+        bytes.extend_from_slice(&16u16.to_le_bytes());
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&data_len.to_le_bytes());
+        for sample in samples {
+            bytes.extend_from_slice(&sample.to_le_bytes());
+        }
+        bytes
+    }
+
+    fn mono(sample_rate: u32, samples: &[f32]) -> Pcm {
+        Pcm { sample_rate, channels: 1, samples: samples.to_vec() }
+    }
+
+    #[test]
+    fn decodes_wav() {
+        // 50 ms of a stereo ramp, the right channel is the left one inverted
+        let samples: Vec<i16> = (0..400).flat_map(|i| [i * 64, -i * 64]).collect();
+        let pcm = Pcm::decode(&wav(8000, 2, &samples)).unwrap();
+
+        assert_eq!(pcm.sample_rate, 8000);
+        assert_eq!(pcm.channels, 2);
+        assert_eq!(pcm.frames(), 400);
+        assert_eq!(pcm.duration(), Duration::from_millis(50));
+        for (decoded, sample) in pcm.samples.iter().zip(&samples) {
+            assert!((decoded - *sample as f32 / 32768.0).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn rejects_other_files() {
+        assert!(Pcm::decode(b"not a sound").is_err());
+    }
+
+    #[test]
+    fn waveform_has_the_range_of_each_column() {
+        let pcm = mono(100, &[0.0, 0.5, -0.25, 1.0, -1.0, 0.2, 0.3, -0.1]);
+        assert_eq!(pcm.waveform(2), vec![[-0.25, 1.0], [-1.0, 0.3]]);
+        assert_eq!(pcm.waveform(4), vec![[0.0, 0.5], [-0.25, 1.0], [-1.0, 0.2], [-0.1, 0.3]]);
+
+        // Every channel counts towards the same column
+        let stereo = Pcm { sample_rate: 100, channels: 2, samples: vec![0.1, -0.6, 0.4, 0.2] };
+        assert_eq!(stereo.waveform(1), vec![[-0.6, 0.4]]);
+    }
+
+    #[test]
+    fn waveform_image_is_drawn_from_the_centre() {
+        let image = mono(100, &[0.5; 8]).waveform_image(4, 10);
+        assert_eq!(image.dimensions(), (4, 10));
+        for x in 0..4 {
+            // Columns start out as silence, so a constant 0.5 is drawn from half way up down to the centre
+            for y in 0..10 {
+                let expected = if (2..5).contains(&y) { WAVEFORM_COLOR } else { Rgba([0, 0, 0, 0]) };
+                assert_eq!(*image.get_pixel(x, y), expected, "pixel {}, {}", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn fill_resamples_and_mixes_to_mono() {
+        let pcm = Pcm { sample_rate: 100, channels: 2, samples: vec![0.0, 1.0, 0.2, 1.2, 0.4, 1.4, 0.6, 1.6] };
+        let mut playback = Playback::new(Arc::new(pcm));
+
+        // Nothing is played before play is pressed
+        let mut output = [1.0; 4];
+        playback.fill(&mut output, 1, 200);
+        assert_eq!(output, [0.0; 4]);
+
+        // Twice the sample rate gives a frame half way between each pair of frames
+        playback.play();
+        let mut output = [1.0; 10];
+        playback.fill(&mut output, 1, 200);
+        let expected = [0.5, 0.6, 0.7, 0.8, 0.9, 1.0, 1.1, 1.1, 0.0, 0.0];
+        for (sample, expected) in output.iter().zip(expected) {
+            assert!((sample - expected).abs() < 1e-6, "{:?}", output);
+        }
+        assert!(!playback.playing, "playing should stop at the end");
+    }
+
+    #[test]
+    fn fill_copies_mono_to_every_output_channel() {
+        let mut playback = Playback::new(Arc::new(mono(100, &[0.25, -0.5])));
+        playback.play();
+        let mut output = [0.0; 4];
+        playback.fill(&mut output, 2, 100);
+        assert_eq!(output, [0.25, 0.25, -0.5, -0.5]);
+    }
+}
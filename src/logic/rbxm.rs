@@ -0,0 +1,716 @@
+// Parser for binary model files (rbxm)
+//
+// Layout (all integers are little endian unless noted):
+//   [u8; 14]       "<roblox!" followed by 89 FF 0D 0A 1A 0A
+//   u16            version
+//   i32            class count
+//   i32            instance count
+//   [u8; 8]        reserved
+//   chunks until "END\0", each chunk is:
+//     [u8; 4]      name
+//     u32          compressed length, 0 if the data is not compressed
+//     u32          uncompressed length
+//     [u8; 4]      reserved
+//     [u8]         data, lz4 or zstd compressed when the compressed length is set
+//
+// Arrays of numbers inside chunks are usually "interleaved": big endian values stored
+// byte plane by byte plane, with integers zigzag encoded and floats rotated
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::io::{Error, ErrorKind, Result};
+use std::path::PathBuf;
+
+use crate::log;
+use super::decompress::{self, Encoding, Limit};
+
+const MAGIC: &[u8; 14] = b"<roblox!\x89\xff\r\n\x1a\n";
+const HEADER_LEN: usize = 32;
+const CHUNK_HEADER_LEN: usize = 16;
+// Largest chunk that is decompressed, the length comes from the file so it can't be trusted
+const MAX_CHUNK_LEN: usize = 256 * 1024 * 1024;
+// LZ4 can't shrink data to less than about 1/255th of its size
+const MAX_LZ4_RATIO: usize = 255;
+
+/// Properties shown next to instances in the tree
+const KEY_PROPERTIES: [&str; 16] = [
+    "MeshId", "MeshContent", "TextureID", "TextureId", "TextureContent", "Texture", "SoundId", "AudioContent",
+    "AnimationId", "Image", "ShirtTemplate", "PantsTemplate", "Graphic", "Size", "size", "Source",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CFrame {
+    pub position: [f32; 3],
+    /// Rotation matrix, row by row
+    pub rotation: [f32; 9],
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhysicalProperties {
+    pub density: f32,
+    pub friction: f32,
+    pub elasticity: f32,
+    pub friction_weight: f32,
+    pub elasticity_weight: f32,
+    pub acoustic_absorption: Option<f32>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Font {
+    pub family: String,
+    pub weight: u16,
+    pub style: u8,
+    pub cached_face_id: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UniqueId {
+    pub random: i64,
+    pub time: u32,
+    pub index: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Content {
+    None,
+    Uri(String),
+    /// Referent of an instance in the model
+    Object(i32),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// Strings can hold binary data, such as attributes
+    String(Vec<u8>),
+    Bool(bool),
+    Int32(i32),
+    Float32(f32),
+    Float64(f64),
+    UDim(f32, i32),
+    UDim2([f32; 2], [i32; 2]),
+    Ray([f32; 3], [f32; 3]),
+    Faces(u8),
+    Axes(u8),
+    BrickColor(u32),
+    Color3([f32; 3]),
+    Vector2([f32; 2]),
+    Vector3([f32; 3]),
+    CFrame(CFrame),
+    Enum(u32),
+    /// Referent of another instance in the model
+    Ref(Option<i32>),
+    Vector3int16([i16; 3]),
+    /// Keypoints of time, value and envelope
+    NumberSequence(Vec<[f32; 3]>),
+    /// Keypoints of time, red, green, blue and envelope
+    ColorSequence(Vec<[f32; 5]>),
+    NumberRange(f32, f32),
+    Rect([f32; 4]),
+    PhysicalProperties(Option<PhysicalProperties>),
+    Color3uint8([u8; 3]),
+    Int64(i64),
+    SharedString(Vec<u8>),
+    Bytecode(Vec<u8>),
+    OptionalCFrame(Option<CFrame>),
+    UniqueId(UniqueId),
+    Font(Font),
+    SecurityCapabilities(u64),
+    Content(Content),
+}
+
+#[derive(Debug, Clone)]
+pub struct Instance {
+    pub referent: i32,
+    pub class_name: String,
+    pub properties: Vec<(String, Value)>,
+    pub parent: Option<usize>,
+    pub children: Vec<usize>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Model {
+    pub instances: Vec<Instance>,
+    /// Instances without a parent, in file order
+    pub roots: Vec<usize>,
+    pub metadata: Vec<(String, String)>,
+    /// MD5 hash and data of strings shared between instances
+    pub shared_strings: Vec<([u8; 16], Vec<u8>)>,
+}
+
+fn invalid(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
+
+pub fn is_rbxm(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC)
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Reader<'a> {
+        Reader { bytes, position: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self.position.checked_add(len).filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| invalid("Model chunk is truncated"))?;
+        let slice = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn f32(&mut self) -> Result<f32> {
+        Ok(f32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn f32s<const N: usize>(&mut self) -> Result<[f32; N]> {
+        let mut values = [0.0; N];
+        for value in values.iter_mut() {
+            *value = self.f32()?;
+        }
+        Ok(values)
+    }
+
+    fn bytes(&mut self) -> Result<Vec<u8>> {
+        let len = self.u32()? as usize;
+        Ok(self.take(len)?.to_vec())
+    }
+
+    fn string(&mut self) -> Result<String> {
+        Ok(String::from_utf8_lossy(&self.bytes()?).to_string())
+    }
+
+    /// Read count values of N bytes stored byte plane by byte plane
+    fn interleaved<const N: usize>(&mut self, count: usize) -> Result<Vec<[u8; N]>> {
+        let data = self.take(count.checked_mul(N).ok_or_else(|| invalid("Model chunk is truncated"))?)?;
+        Ok((0..count).map(|i| std::array::from_fn(|byte| data[byte * count + i])).collect())
+    }
+
+    fn interleaved_u32(&mut self, count: usize) -> Result<Vec<u32>> {
+        Ok(self.interleaved::<4>(count)?.into_iter().map(u32::from_be_bytes).collect())
+    }
+
+    fn interleaved_i32(&mut self, count: usize) -> Result<Vec<i32>> {
+        Ok(self.interleaved_u32(count)?.into_iter().map(|v| (v >> 1) as i32 ^ -((v & 1) as i32)).collect())
+    }
+
+    fn interleaved_i64(&mut self, count: usize) -> Result<Vec<i64>> {
+        Ok(self.interleaved::<8>(count)?.into_iter().map(u64::from_be_bytes)
+            .map(|v| (v >> 1) as i64 ^ -((v & 1) as i64)).collect())
+    }
+
+    fn interleaved_f32(&mut self, count: usize) -> Result<Vec<f32>> {
+        Ok(self.interleaved_u32(count)?.into_iter().map(|v| f32::from_bits(v.rotate_right(1))).collect())
+    }
+
+    /// Referents are stored as the difference to the previous one
+    fn referents(&mut self, count: usize) -> Result<Vec<i32>> {
+        let mut last = 0i32;
+        Ok(self.interleaved_i32(count)?.into_iter().map(|delta| {
+            last = last.wrapping_add(delta);
+            last
+        }).collect())
+    }
+}
+
+fn read_chunk<'a>(reader: &mut Reader<'a>) -> Result<([u8; 4], Vec<u8>)> {
+    let header = reader.take(CHUNK_HEADER_LEN)?;
+    let name: [u8; 4] = header[0..4].try_into().unwrap();
+    let compressed_len = u32::from_le_bytes(header[4..8].try_into().unwrap()) as usize;
+    let uncompressed_len = u32::from_le_bytes(header[8..12].try_into().unwrap()) as usize;
+
+    if compressed_len == 0 {
+        return Ok((name, reader.take(uncompressed_len)?.to_vec()))
+    }
+
+    if uncompressed_len > MAX_CHUNK_LEN {
+        return Err(invalid("Model chunk is too large"));
+    }
+    let data = reader.take(compressed_len)?;
+    let decompressed = if decompress::sniff_encoding(data) == Some(Encoding::Zstd) {
+        decompress::decode(data, &[Encoding::Zstd], Limit::Max(uncompressed_len))?
+    } else {
+        // The output is allocated up front, so a length the data can't decode to is rejected first
+        if uncompressed_len > compressed_len.saturating_mul(MAX_LZ4_RATIO) {
+            return Err(invalid("Model chunk is larger than its compressed data can hold"));
+        }
+        lz4_flex::block::decompress(data, uncompressed_len).map_err(|e| invalid(&e.to_string()))?
+    };
+    if decompressed.len() != uncompressed_len {
+        return Err(invalid("Model chunk has the wrong size after decompressing"));
+    }
+    Ok((name, decompressed))
+}
+
+fn basic_rotation(id: u8) -> Option<[f32; 9]> {
+    // The id packs the direction of the first two columns, the last column is their cross product
+    const NORMALS: [[f32; 3]; 6] = [
+        [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0],
+        [-1.0, 0.0, 0.0], [0.0, -1.0, 0.0], [0.0, 0.0, -1.0],
+    ];
+    let id = id.checked_sub(1)? as usize;
+    let (x, y) = (NORMALS.get(id / 6)?, NORMALS[id % 6]);
+    let z = [x[1] * y[2] - x[2] * y[1], x[2] * y[0] - x[0] * y[2], x[0] * y[1] - x[1] * y[0]].map(|v| v + 0.0); // No negative zeros
+    Some([x[0], y[0], z[0], x[1], y[1], z[1], x[2], y[2], z[2]])
+}
+
+fn read_cframes(reader: &mut Reader, count: usize) -> Result<Vec<CFrame>> {
+    let mut rotations = Vec::with_capacity(count);
+    for _ in 0..count {
+        let id = reader.u8()?;
+        rotations.push(if id == 0 {
+            reader.f32s::<9>()?
+        } else {
+            basic_rotation(id).ok_or_else(|| invalid("Invalid CFrame rotation"))?
+        });
+    }
+    let (x, y, z) = (reader.interleaved_f32(count)?, reader.interleaved_f32(count)?, reader.interleaved_f32(count)?);
+    Ok(rotations.into_iter().enumerate().map(|(i, rotation)| CFrame { position: [x[i], y[i], z[i]], rotation }).collect())
+}
+
+fn read_values(reader: &mut Reader, value_type: u8, count: usize, shared_strings: &[([u8; 16], Vec<u8>)]) -> Result<Vec<Value>> {
+    let values = match value_type {
+        0x01 => (0..count).map(|_| reader.bytes().map(Value::String)).collect::<Result<_>>()?,
+        0x02 => reader.take(count)?.iter().map(|b| Value::Bool(*b != 0)).collect(),
+        0x03 => reader.interleaved_i32(count)?.into_iter().map(Value::Int32).collect(),
+        0x04 => reader.interleaved_f32(count)?.into_iter().map(Value::Float32).collect(),
+        0x05 => (0..count).map(|_| Ok(Value::Float64(f64::from_le_bytes(reader.take(8)?.try_into().unwrap())))).collect::<Result<_>>()?,
+        0x06 => {
+            let (scale, offset) = (reader.interleaved_f32(count)?, reader.interleaved_i32(count)?);
+            (0..count).map(|i| Value::UDim(scale[i], offset[i])).collect()
+        }
+        0x07 => {
+            let (scale_x, scale_y) = (reader.interleaved_f32(count)?, reader.interleaved_f32(count)?);
+            let (offset_x, offset_y) = (reader.interleaved_i32(count)?, reader.interleaved_i32(count)?);
+            (0..count).map(|i| Value::UDim2([scale_x[i], scale_y[i]], [offset_x[i], offset_y[i]])).collect()
+        }
+        0x08 => (0..count).map(|_| Ok(Value::Ray(reader.f32s()?, reader.f32s()?))).collect::<Result<_>>()?,
+        0x09 => reader.take(count)?.iter().map(|b| Value::Faces(*b)).collect(),
+        0x0A => reader.take(count)?.iter().map(|b| Value::Axes(*b)).collect(),
+        0x0B => reader.interleaved_u32(count)?.into_iter().map(Value::BrickColor).collect(),
+        0x0C => {
+            let (r, g, b) = (reader.interleaved_f32(count)?, reader.interleaved_f32(count)?, reader.interleaved_f32(count)?);
+            (0..count).map(|i| Value::Color3([r[i], g[i], b[i]])).collect()
+        }
+        0x0D => {
+            let (x, y) = (reader.interleaved_f32(count)?, reader.interleaved_f32(count)?);
+            (0..count).map(|i| Value::Vector2([x[i], y[i]])).collect()
+        }
+        0x0E => {
+            let (x, y, z) = (reader.interleaved_f32(count)?, reader.interleaved_f32(count)?, reader.interleaved_f32(count)?);
+            (0..count).map(|i| Value::Vector3([x[i], y[i], z[i]])).collect()
+        }
+        0x10 => read_cframes(reader, count)?.into_iter().map(Value::CFrame).collect(),
+        0x12 => reader.interleaved_u32(count)?.into_iter().map(Value::Enum).collect(),
+        0x13 => reader.referents(count)?.into_iter().map(|r| Value::Ref(if r == -1 { None } else { Some(r) })).collect(),
+        0x14 => (0..count).map(|_| {
+            let bytes = reader.take(6)?;
+            Ok(Value::Vector3int16(std::array::from_fn(|i| i16::from_le_bytes([bytes[i * 2], bytes[i * 2 + 1]]))))
+        }).collect::<Result<_>>()?,
+        0x15 => (0..count).map(|_| {
+            let keypoints = reader.u32()?;
+            Ok(Value::NumberSequence((0..keypoints).map(|_| reader.f32s()).collect::<Result<_>>()?))
+        }).collect::<Result<_>>()?,
+        0x16 => (0..count).map(|_| {
+            let keypoints = reader.u32()?;
+            Ok(Value::ColorSequence((0..keypoints).map(|_| reader.f32s()).collect::<Result<_>>()?))
+        }).collect::<Result<_>>()?,
+        0x17 => (0..count).map(|_| Ok(Value::NumberRange(reader.f32()?, reader.f32()?))).collect::<Result<_>>()?,
+        0x18 => {
+            let planes = [reader.interleaved_f32(count)?, reader.interleaved_f32(count)?, reader.interleaved_f32(count)?, reader.interleaved_f32(count)?];
+            (0..count).map(|i| Value::Rect(planes.each_ref().map(|plane| plane[i]))).collect()
+        }
+        0x19 => (0..count).map(|_| {
+            let flags = reader.u8()?;
+            if flags & 1 == 0 {
+                return Ok(Value::PhysicalProperties(None))
+            }
+            let [density, friction, elasticity, friction_weight, elasticity_weight] = reader.f32s()?;
+            let acoustic_absorption = if flags & 2 != 0 { Some(reader.f32()?) } else { None };
+            Ok(Value::PhysicalProperties(Some(PhysicalProperties {
+                density, friction, elasticity, friction_weight, elasticity_weight, acoustic_absorption,
+            })))
+        }).collect::<Result<_>>()?,
+        0x1A => {
+            let (r, g, b) = (reader.take(count)?, reader.take(count)?, reader.take(count)?);
+            (0..count).map(|i| Value::Color3uint8([r[i], g[i], b[i]])).collect()
+        }
+        0x1B => reader.interleaved_i64(count)?.into_iter().map(Value::Int64).collect(),
+        0x1C => reader.interleaved_u32(count)?.into_iter().map(|index| {
+            shared_strings.get(index as usize).map(|(_, data)| Value::SharedString(data.clone())).ok_or_else(|| invalid("Missing shared string"))
+        }).collect::<Result<_>>()?,
+        0x1D => (0..count).map(|_| reader.bytes().map(Value::Bytecode)).collect::<Result<_>>()?,
+        0x1E => {
+            if reader.u8()? != 0x10 {
+                return Err(invalid("Invalid OptionalCFrame"));
+            }
+            let cframes = read_cframes(reader, count)?;
+            if reader.u8()? != 0x02 {
+                return Err(invalid("Invalid OptionalCFrame"));
+            }
+            let present = reader.take(count)?;
+            cframes.into_iter().zip(present).map(|(cframe, present)| Value::OptionalCFrame((*present != 0).then_some(cframe))).collect()
+        }
+        // Index, time and random, with the random part rotated like floats
+        0x1F => reader.interleaved::<16>(count)?.into_iter().map(|bytes| Value::UniqueId(UniqueId {
+            index: u32::from_be_bytes(bytes[0..4].try_into().unwrap()),
+            time: u32::from_be_bytes(bytes[4..8].try_into().unwrap()),
+            random: i64::from_be_bytes(bytes[8..16].try_into().unwrap()).rotate_right(1),
+        })).collect(),
+        0x20 => (0..count).map(|_| Ok(Value::Font(Font {
+            family: reader.string()?,
+            weight: reader.u16()?,
+            style: reader.u8()?,
+            cached_face_id: reader.string()?,
+        }))).collect::<Result<_>>()?,
+        0x21 => reader.interleaved_i64(count)?.into_iter().map(|v| Value::SecurityCapabilities(v as u64)).collect(),
+        0x22 => {
+            let sources = reader.interleaved_i32(count)?;
+            let uris = (0..reader.u32()?).map(|_| reader.string()).collect::<Result<Vec<_>>>()?;
+            let object_count = reader.u32()? as usize;
+            let objects = reader.referents(object_count)?;
+            // Followed by external object referents, which are only used internally by Roblox
+            let (mut uris, mut objects) = (uris.into_iter(), objects.into_iter());
+            sources.into_iter().map(|source| match source {
+                0 => Ok(Value::Content(Content::None)),
+                1 => uris.next().map(|uri| Value::Content(Content::Uri(uri))).ok_or_else(|| invalid("Missing content URI")),
+                2 => objects.next().map(|object| Value::Content(Content::Object(object))).ok_or_else(|| invalid("Missing content object")),
+                _ => Err(invalid("Invalid content source")),
+            }).collect::<Result<_>>()?
+        }
+        _ => return Err(Error::new(ErrorKind::Unsupported, format!("Unsupported property type 0x{:02X}", value_type))),
+    };
+    Ok(values)
+}
+
+impl Model {
+    pub fn parse(bytes: &[u8]) -> Result<Model> {
+        if !is_rbxm(bytes) || bytes.len() < HEADER_LEN {
+            return Err(invalid("Not a binary model"));
+        }
+        let class_count = u32::from_le_bytes(bytes[16..20].try_into().unwrap()) as usize;
+        let mut reader = Reader::new(&bytes[HEADER_LEN..]);
+
+        let mut model = Model::default();
+        let mut classes: HashMap<usize, (String, Vec<usize>)> = HashMap::new(); // Class name and instances by class id
+        let mut referents = HashMap::new();
+        let mut parents = Vec::new();
+
+        loop {
+            let (name, data) = read_chunk(&mut reader)?;
+            let mut chunk = Reader::new(&data);
+            match &name {
+                b"META" => {
+                    for _ in 0..chunk.u32()? {
+                        model.metadata.push((chunk.string()?, chunk.string()?));
+                    }
+                }
+                b"SSTR" => {
+                    let _version = chunk.u32()?;
+                    for _ in 0..chunk.u32()? {
+                        let hash = chunk.take(16)?.try_into().unwrap();
+                        model.shared_strings.push((hash, chunk.bytes()?));
+                    }
+                }
+                b"INST" => {
+                    let class_id = chunk.u32()? as usize;
+                    if class_id >= class_count {
+                        return Err(invalid("Instance chunk for a class outside the class count"));
+                    }
+                    let class_name = chunk.string()?;
+                    let _is_service = chunk.u8()?;
+                    let count = chunk.u32()? as usize;
+
+                    // Reading the referents first makes sure the chunk really holds that many instances
+                    let instance_referents = chunk.referents(count)?;
+                    let mut instances = Vec::with_capacity(instance_referents.len());
+                    for referent in instance_referents {
+                        referents.insert(referent, model.instances.len());
+                        instances.push(model.instances.len());
+                        model.instances.push(Instance {
+                            referent,
+                            class_name: class_name.clone(),
+                            properties: Vec::new(),
+                            parent: None,
+                            children: Vec::new(),
+                        });
+                    }
+                    classes.insert(class_id, (class_name, instances));
+                }
+                b"PROP" => {
+                    let class_id = chunk.u32()? as usize;
+                    let property = chunk.string()?;
+                    let value_type = chunk.u8()?;
+                    let Some((class_name, instances)) = classes.get(&class_id) else {
+                        return Err(invalid("Property chunk for an unknown class"));
+                    };
+                    // Unknown types only lose that property, chunks are independent of each other
+                    match read_values(&mut chunk, value_type, instances.len(), &model.shared_strings) {
+                        Ok(values) => {
+                            for (instance, value) in instances.iter().zip(values) {
+                                model.instances[*instance].properties.push((property.clone(), value));
+                            }
+                        }
+                        Err(e) => log::warn(&format!("Skipped property {}.{}: {}", class_name, property, e)),
+                    }
+                }
+                b"PRNT" => {
+                    let _version = chunk.u8()?;
+                    let count = chunk.u32()? as usize;
+                    let children = chunk.referents(count)?;
+                    let parent_referents = chunk.referents(count)?;
+                    parents.extend(children.into_iter().zip(parent_referents));
+                }
+                b"END\0" => break,
+                _ => (), // Chunks from newer versions that we don't need
+            }
+        }
+
+        // Build the tree, instances keep the order they were listed in. A child listed again keeps its first parent.
+        let mut edges = Vec::new();
+        for (child, parent) in parents {
+            let (Some(child), Some(parent)) = (referents.get(&child).copied(), referents.get(&parent).copied()) else {
+                continue
+            };
+            if model.instances[child].parent.is_none() {
+                model.instances[child].parent = Some(parent);
+                edges.push((child, parent));
+            }
+        }
+        model.break_cycles();
+        for (child, parent) in edges {
+            if model.instances[child].parent == Some(parent) {
+                model.instances[parent].children.push(child);
+            }
+        }
+        model.roots = (0..model.instances.len()).filter(|i| model.instances[*i].parent.is_none()).collect();
+
+        Ok(model)
+    }
+
+    /// Instances that are their own ancestor would never end when the tree is walked,
+    /// the parent that closes the loop is dropped so they become roots
+    fn break_cycles(&mut self) {
+        // 0: not visited yet, 1: on the path being walked, 2: known to lead to a root
+        let mut states = vec![0u8; self.instances.len()];
+        for start in 0..self.instances.len() {
+            let mut path = Vec::new();
+            let mut index = start;
+            while states[index] == 0 {
+                states[index] = 1;
+                path.push(index);
+                match self.instances[index].parent {
+                    Some(parent) => index = parent,
+                    None => break,
+                }
+            }
+            if states[index] == 1 && self.instances[index].parent.is_some() {
+                self.instances[index].parent = None;
+            }
+            for index in path {
+                states[index] = 2;
+            }
+        }
+    }
+
+    pub fn find_referent(&self, referent: i32) -> Option<&Instance> {
+        self.instances.iter().find(|instance| instance.referent == referent)
+    }
+
+    /// Sources of every script, with paths that mirror the instance tree
+    pub fn script_files(&self) -> Vec<(PathBuf, &[u8])> {
+        let mut files = Vec::new();
+        let mut used_names = HashSet::new();
+        for root in &self.roots {
+            self.script_files_of(*root, PathBuf::new(), &mut used_names, &mut files);
+        }
+        files
+    }
+
+    fn script_files_of<'a>(&'a self, index: usize, directory: PathBuf, used_names: &mut HashSet<PathBuf>, files: &mut Vec<(PathBuf, &'a [u8])>) {
+        let instance = &self.instances[index];
+
+        // Siblings can share a name, the later ones get a number so they don't overwrite each other
+        let name = file_name(&instance.name());
+        let mut path = directory.join(&name);
+        let mut number = 1;
+        while !used_names.insert(path.clone()) {
+            number += 1;
+            path = directory.join(format!("{} ({})", name, number));
+        }
+
+        // Suffixes used by Rojo, so the class isn't lost
+        let suffix = match instance.class_name.as_str() {
+            "Script" => Some(".server.lua"),
+            "LocalScript" => Some(".client.lua"),
+            "ModuleScript" => Some(".lua"),
+            _ => None,
+        };
+        if let (Some(suffix), Some(Value::String(source))) = (suffix, instance.property("Source")) {
+            let mut file = path.clone().into_os_string();
+            file.push(suffix);
+            files.push((file.into(), source));
+        }
+
+        for child in &instance.children {
+            self.script_files_of(*child, path.clone(), used_names, files);
+        }
+    }
+
+    /// Print the instance tree as indented text
+    pub fn format_tree(&self) -> String {
+        let mut output = String::new();
+        for root in &self.roots {
+            self.format_instance(*root, 0, &mut output);
+        }
+        output
+    }
+
+    fn format_instance(&self, index: usize, depth: usize, output: &mut String) {
+        let instance = &self.instances[index];
+        let indent = "  ".repeat(depth);
+        output.push_str(&format!("{}{}\n", indent, instance.label()));
+        for (name, value) in instance.key_properties() {
+            output.push_str(&format!("{}  - {}: {}\n", indent, name, value));
+        }
+        for child in &instance.children {
+            self.format_instance(*child, depth + 1, output);
+        }
+    }
+}
+
+impl Instance {
+    pub fn property(&self, name: &str) -> Option<&Value> {
+        self.properties.iter().find(|(key, _)| key == name).map(|(_, value)| value)
+    }
+
+    pub fn name(&self) -> String {
+        match self.property("Name") {
+            Some(Value::String(name)) => String::from_utf8_lossy(name).to_string(),
+            _ => self.class_name.clone(),
+        }
+    }
+
+    /// Name and class of the instance, e.g. `Handle (Part)`
+    pub fn label(&self) -> String {
+        format!("{} ({})", self.name(), self.class_name)
+    }
+
+    pub fn key_properties(&self) -> Vec<(&str, &Value)> {
+        KEY_PROPERTIES.iter()
+            .filter_map(|name| self.property(name).map(|value| (*name, value)))
+            .filter(|(_, value)| !matches!(value, Value::String(s) | Value::SharedString(s) if s.is_empty()))
+            .filter(|(_, value)| !matches!(value, Value::Content(Content::None)))
+            .collect()
+    }
+}
+
+/// Instance names can contain anything, file names can't
+fn file_name(name: &str) -> String {
+    let name: String = name.chars()
+        .map(|c| if c.is_control() || matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|') { '_' } else { c })
+        .collect();
+    let name = name.trim_end_matches(['.', ' ']); // Windows drops these
+    if name.is_empty() {
+        "_".to_owned()
+    } else {
+        name.to_owned()
+    }
+}
+
+fn format_floats(values: &[f32]) -> String {
+    values.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(", ")
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            // Long strings such as script sources are summarised
+            Value::String(bytes) | Value::SharedString(bytes) => {
+                let text = String::from_utf8_lossy(bytes);
+                if text.contains('\n') || text.len() > 120 {
+                    write!(f, "{} lines, {} bytes", text.lines().count(), bytes.len())
+                } else {
+                    write!(f, "{}", text)
+                }
+            }
+            Value::Bytecode(bytes) => write!(f, "{} bytes of bytecode", bytes.len()),
+            Value::Bool(v) => write!(f, "{}", v),
+            Value::Int32(v) => write!(f, "{}", v),
+            Value::Float32(v) => write!(f, "{}", v),
+            Value::Float64(v) => write!(f, "{}", v),
+            Value::UDim(scale, offset) => write!(f, "{}, {}", scale, offset),
+            Value::UDim2(scale, offset) => write!(f, "{{{}, {}}}, {{{}, {}}}", scale[0], offset[0], scale[1], offset[1]),
+            Value::Ray(origin, direction) => write!(f, "{{{}}}, {{{}}}", format_floats(origin), format_floats(direction)),
+            Value::Faces(v) | Value::Axes(v) => write!(f, "{:06b}", v),
+            Value::BrickColor(v) | Value::Enum(v) => write!(f, "{}", v),
+            Value::Color3(v) => write!(f, "{}", format_floats(v)),
+            Value::Vector2(v) => write!(f, "{}", format_floats(v)),
+            Value::Vector3(v) => write!(f, "{}", format_floats(v)),
+            Value::CFrame(cframe) | Value::OptionalCFrame(Some(cframe)) => {
+                write!(f, "{}, {}", format_floats(&cframe.position), format_floats(&cframe.rotation))
+            }
+            Value::OptionalCFrame(None) | Value::Ref(None) | Value::PhysicalProperties(None) => write!(f, "nil"),
+            Value::Ref(Some(referent)) => write!(f, "RBX{}", referent),
+            Value::Vector3int16(v) => write!(f, "{}, {}, {}", v[0], v[1], v[2]),
+            Value::NumberSequence(keypoints) => write!(f, "{} keypoints", keypoints.len()),
+            Value::ColorSequence(keypoints) => write!(f, "{} keypoints", keypoints.len()),
+            Value::NumberRange(min, max) => write!(f, "{} {}", min, max),
+            Value::Rect(v) => write!(f, "{}", format_floats(v)),
+            Value::PhysicalProperties(Some(p)) => write!(f, "{}, {}, {}, {}, {}", p.density, p.friction, p.elasticity, p.friction_weight, p.elasticity_weight),
+            Value::Color3uint8(v) => write!(f, "{}, {}, {}", v[0], v[1], v[2]),
+            Value::Int64(v) => write!(f, "{}", v),
+            Value::UniqueId(id) => write!(f, "{:016x}{:08x}{:08x}", id.random, id.time, id.index),
+            Value::Font(font) => write!(f, "{} ({}, {})", font.family, font.weight, font.style),
+            Value::SecurityCapabilities(v) => write!(f, "{}", v),
+            Value::Content(Content::None) => write!(f, "nil"),
+            Value::Content(Content::Uri(uri)) => write!(f, "{}", uri),
+            Value::Content(Content::Object(referent)) => write!(f, "RBX{}", referent),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instance(parent: Option<usize>) -> Instance {
+        Instance { referent: 0, class_name: String::new(), properties: Vec::new(), parent, children: Vec::new() }
+    }
+
+    #[test]
+    fn breaks_parent_cycles() {
+        // 0 is a root, 1 and 2 are each other's parent, 3 hangs off the loop
+        let mut model = Model {
+            instances: vec![instance(None), instance(Some(2)), instance(Some(1)), instance(Some(2))],
+            ..Default::default()
+        };
+        model.break_cycles();
+        assert_eq!(model.instances[0].parent, None);
+        assert_eq!(model.instances.iter().filter(|instance| instance.parent.is_none()).count(), 2);
+        for start in 0..model.instances.len() {
+            let mut index = start;
+            let mut steps = 0;
+            while let Some(parent) = model.instances[index].parent {
+                index = parent;
+                steps += 1;
+                assert!(steps < model.instances.len());
+            }
+        }
+    }
+}
@@ -0,0 +1,215 @@
+// Writer for XML model files (rbxmx)
+//
+// Binary models store scripts, asset urls and binary blobs all as strings, the XML format
+// gives each its own type. The binary file doesn't say which one a property is, so they
+// are told apart by the property name.
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+
+use super::rbxm::{CFrame, Content, Model, Value};
+
+/// String properties that hold binary data
+const BINARY_STRING_PROPERTIES: [&str; 10] = [
+    "AttributesSerialize", "Tags", "PhysicsData", "MeshData", "ChildData", "SmoothGrid",
+    "MaterialColors", "PhysicalConfigData", "ModelMeshData", "LODData",
+];
+
+/// String properties that hold asset urls, besides the ones ending in "Id"
+const CONTENT_PROPERTIES: [&str; 19] = [
+    "Image", "Texture", "ShirtTemplate", "PantsTemplate", "Graphic", "LinkedSource", "HoverImage",
+    "PressedImage", "ColorMap", "MetalnessMap", "NormalMap", "RoughnessMap", "SkyboxBk", "SkyboxDn",
+    "SkyboxFt", "SkyboxLf", "SkyboxRt", "SkyboxUp", "CursorIcon",
+];
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\r' => escaped.push_str("&#13;"), // Would be turned into \n when read otherwise
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Control characters other than whitespace can't be stored in XML at all
+fn is_xml_text(text: &str) -> bool {
+    !text.chars().any(|c| c < ' ' && !matches!(c, '\t' | '\n' | '\r'))
+}
+
+fn float(value: f32) -> String {
+    if value.is_nan() {
+        "NAN".to_owned()
+    } else if value.is_infinite() {
+        if value > 0.0 { "INF".to_owned() } else { "-INF".to_owned() }
+    } else {
+        value.to_string()
+    }
+}
+
+fn elements(names: &[&str], values: &[String]) -> String {
+    names.iter().zip(values).map(|(name, value)| format!("<{0}>{1}</{0}>", name, value)).collect()
+}
+
+fn floats(names: &[&str], values: &[f32]) -> String {
+    elements(names, &values.iter().map(|v| float(*v)).collect::<Vec<_>>())
+}
+
+fn url(url: &str) -> String {
+    if url.is_empty() {
+        "<null></null>".to_owned()
+    } else {
+        format!("<url>{}</url>", escape(url))
+    }
+}
+
+fn cframe(cframe: &CFrame) -> String {
+    floats(&["X", "Y", "Z"], &cframe.position)
+        + &floats(&["R00", "R01", "R02", "R10", "R11", "R12", "R20", "R21", "R22"], &cframe.rotation)
+}
+
+/// The hash is used as the key, but newer files leave it zeroed
+fn shared_string_key(model: &Model, index: usize) -> String {
+    let hash = model.shared_strings[index].0;
+    if model.shared_strings.iter().filter(|(other, _)| *other == hash).count() == 1 {
+        BASE64.encode(hash)
+    } else {
+        BASE64.encode((index as u128).to_be_bytes())
+    }
+}
+
+fn is_content_property(name: &str) -> bool {
+    CONTENT_PROPERTIES.contains(&name) || name.ends_with("Id") || name.ends_with("ID")
+}
+
+/// Tag and contents of a property
+fn property(model: &Model, name: &str, value: &Value) -> (&'static str, String) {
+    match value {
+        Value::String(bytes) => match std::str::from_utf8(bytes) {
+            Ok(text) if is_xml_text(text) && !BINARY_STRING_PROPERTIES.contains(&name) => {
+                if name == "Source" {
+                    // Scripts are kept readable in CDATA, unless they contain something CDATA can't hold
+                    if text.contains("]]>") || text.contains('\r') {
+                        ("ProtectedString", escape(text))
+                    } else {
+                        ("ProtectedString", format!("<![CDATA[{}]]>", text))
+                    }
+                } else if is_content_property(name) {
+                    ("Content", url(text))
+                } else {
+                    ("string", escape(text))
+                }
+            }
+            _ => ("BinaryString", BASE64.encode(bytes)),
+        },
+        Value::Bool(v) => ("bool", v.to_string()),
+        Value::Int32(v) => ("int", v.to_string()),
+        Value::Float32(v) => ("float", float(*v)),
+        Value::Float64(v) => ("double", if v.is_finite() { v.to_string() } else { float(*v as f32) }),
+        Value::UDim(scale, offset) => ("UDim", elements(&["S", "O"], &[float(*scale), offset.to_string()])),
+        Value::UDim2(scale, offset) => ("UDim2", elements(
+            &["XS", "XO", "YS", "YO"],
+            &[float(scale[0]), offset[0].to_string(), float(scale[1]), offset[1].to_string()],
+        )),
+        Value::Ray(origin, direction) => ("Ray", elements(
+            &["origin", "direction"],
+            &[floats(&["X", "Y", "Z"], origin), floats(&["X", "Y", "Z"], direction)],
+        )),
+        Value::Faces(v) => ("Faces", elements(&["faces"], &[v.to_string()])),
+        Value::Axes(v) => ("Axes", elements(&["axes"], &[v.to_string()])),
+        Value::BrickColor(v) => ("int", v.to_string()),
+        Value::Color3(v) => ("Color3", floats(&["R", "G", "B"], v)),
+        Value::Vector2(v) => ("Vector2", floats(&["X", "Y"], v)),
+        Value::Vector3(v) => ("Vector3", floats(&["X", "Y", "Z"], v)),
+        Value::CFrame(v) => ("CoordinateFrame", cframe(v)),
+        Value::Enum(v) => ("token", v.to_string()),
+        Value::Ref(referent) => ("Ref", referent.map(|r| format!("RBX{}", r)).unwrap_or("null".to_owned())),
+        Value::Vector3int16(v) => ("Vector3int16", elements(&["X", "Y", "Z"], &v.map(|v| v.to_string()))),
+        Value::NumberSequence(keypoints) => ("NumberSequence", keypoints.iter().flatten().map(|v| float(*v) + " ").collect()),
+        Value::ColorSequence(keypoints) => ("ColorSequence", keypoints.iter().flatten().map(|v| float(*v) + " ").collect()),
+        Value::NumberRange(min, max) => ("NumberRange", format!("{} {} ", float(*min), float(*max))),
+        Value::Rect(v) => ("Rect2D", elements(
+            &["min", "max"],
+            &[floats(&["X", "Y"], &v[0..2]), floats(&["X", "Y"], &v[2..4])],
+        )),
+        Value::PhysicalProperties(None) => ("PhysicalProperties", elements(&["CustomPhysics"], &["false".to_owned()])),
+        Value::PhysicalProperties(Some(p)) => {
+            let mut contents = elements(&["CustomPhysics"], &["true".to_owned()]) + &floats(
+                &["Density", "Friction", "Elasticity", "FrictionWeight", "ElasticityWeight"],
+                &[p.density, p.friction, p.elasticity, p.friction_weight, p.elasticity_weight],
+            );
+            if let Some(acoustic_absorption) = p.acoustic_absorption {
+                contents += &floats(&["AcousticAbsorption"], &[acoustic_absorption]);
+            }
+            ("PhysicalProperties", contents)
+        }
+        // Packed as ARGB, like Roblox Studio does
+        Value::Color3uint8([r, g, b]) => ("Color3uint8", (0xFF000000 | (*r as u32) << 16 | (*g as u32) << 8 | *b as u32).to_string()),
+        Value::Int64(v) => ("int64", v.to_string()),
+        Value::SharedString(data) => {
+            let index = model.shared_strings.iter().position(|(_, shared)| shared == data).unwrap_or_default();
+            ("SharedString", shared_string_key(model, index))
+        }
+        Value::Bytecode(bytes) => ("BinaryString", BASE64.encode(bytes)),
+        Value::OptionalCFrame(v) => ("OptionalCoordinateFrame", v.as_ref().map(|v| elements(&["CFrame"], &[cframe(v)])).unwrap_or_default()),
+        Value::UniqueId(id) => ("UniqueId", format!("{:016x}{:08x}{:08x}", id.random, id.time, id.index)),
+        Value::Font(font) => {
+            let mut contents = elements(
+                &["Family", "Weight", "Style"],
+                &[url(&font.family), font.weight.to_string(), if font.style == 1 { "Italic" } else { "Normal" }.to_owned()],
+            );
+            if !font.cached_face_id.is_empty() {
+                contents += &elements(&["CachedFaceId"], &[url(&font.cached_face_id)]);
+            }
+            ("Font", contents)
+        }
+        Value::SecurityCapabilities(v) => ("SecurityCapabilities", v.to_string()),
+        Value::Content(Content::Uri(uri)) => ("Content", format!("<uri>{}</uri>", escape(uri))),
+        // Objects only exist while the game is running, so they are never saved
+        Value::Content(Content::None | Content::Object(_)) => ("Content", "<null></null>".to_owned()),
+    }
+}
+
+fn write_item(model: &Model, index: usize, depth: usize, output: &mut String) {
+    let instance = &model.instances[index];
+    let indent = "\t".repeat(depth);
+    output.push_str(&format!("{}<Item class=\"{}\" referent=\"RBX{}\">\n", indent, escape(&instance.class_name), instance.referent));
+    output.push_str(&format!("{}\t<Properties>\n", indent));
+    for (name, value) in &instance.properties {
+        let (tag, contents) = property(model, name, value);
+        output.push_str(&format!("{indent}\t\t<{tag} name=\"{}\">{contents}</{tag}>\n", escape(name)));
+    }
+    output.push_str(&format!("{}\t</Properties>\n", indent));
+    for child in &instance.children {
+        write_item(model, *child, depth + 1, output);
+    }
+    output.push_str(&format!("{}</Item>\n", indent));
+}
+
+pub fn write(model: &Model) -> String {
+    let mut output = String::from(concat!(
+        "<roblox xmlns:xmime=\"http://www.w3.org/2005/05/xmlmime\" ",
+        "xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" ",
+        "xsi:noNamespaceSchemaLocation=\"http://www.roblox.com/roblox.xsd\" version=\"4\">\n",
+    ));
+    for (key, value) in &model.metadata {
+        output.push_str(&format!("\t<Meta name=\"{}\">{}</Meta>\n", escape(key), escape(value)));
+    }
+    output.push_str("\t<External>null</External>\n\t<External>nil</External>\n");
+    for root in &model.roots {
+        write_item(model, *root, 1, &mut output);
+    }
+    if !model.shared_strings.is_empty() {
+        output.push_str("\t<SharedStrings>\n");
+        for (index, (_, data)) in model.shared_strings.iter().enumerate() {
+            output.push_str(&format!("\t\t<SharedString md5=\"{}\">{}</SharedString>\n", shared_string_key(model, index), BASE64.encode(data)));
+        }
+        output.push_str("\t</SharedStrings>\n");
+    }
+    output.push_str("</roblox>\n");
+    output
+}
@@ -0,0 +1,128 @@
+// Software rasteriser for mesh thumbnails, so previews work without a GPU
+//
+// The full quality LOD is drawn from the front, turned a little to show its depth, with
+// flat shading and a depth buffer. It is drawn at twice the size and scaled down to smooth
+// the edges.
+use image::{imageops, Rgba, RgbaImage};
+
+use super::mesh::Mesh;
+
+const SUPERSAMPLE: u32 = 2;
+const MARGIN: f32 = 0.08;
+const YAW: f32 = 0.6; // Radians around the up axis
+const PITCH: f32 = 0.35; // Radians looking down at the mesh
+const LIGHT: [f32; 3] = [-0.4, 0.6, 0.7];
+const AMBIENT: f32 = 0.3;
+const BASE_COLOR: [f32; 3] = [0.75, 0.77, 0.8];
+
+fn normalize(v: [f32; 3]) -> [f32; 3] {
+    let length = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+    if length > 0.0 { v.map(|c| c / length) } else { v }
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
+}
+
+/// Turn a point to face the camera, x goes right, y up and z towards the camera
+fn view(position: [f32; 3]) -> [f32; 3] {
+    let [x, y, z] = position;
+    // The front of a model faces -Z, so the camera looks at it from there
+    let (sin, cos) = YAW.sin_cos();
+    let (x, z) = (-(x * cos + z * sin), -(z * cos - x * sin));
+    let (sin, cos) = PITCH.sin_cos();
+    (x, y * cos - z * sin, z * cos + y * sin).into()
+}
+
+pub fn render(mesh: &Mesh, size: u32) -> RgbaImage {
+    let full_size = size.max(1) * SUPERSAMPLE;
+    let mut image = RgbaImage::new(full_size, full_size);
+    let faces = match mesh.lods.first() {
+        Some(faces) => &mesh.faces[faces.clone()],
+        None => &mesh.faces[..],
+    };
+    if mesh.vertices.is_empty() || faces.is_empty() {
+        return image;
+    }
+
+    let points: Vec<[f32; 3]> = mesh.vertices.iter().map(|vertex| view(vertex.position)).collect();
+
+    // Fit the mesh into the image, keeping its aspect ratio
+    let mut min = [f32::INFINITY; 2];
+    let mut max = [f32::NEG_INFINITY; 2];
+    for point in faces.iter().flatten().map(|i| points[*i as usize]) {
+        for axis in 0..2 {
+            min[axis] = min[axis].min(point[axis]);
+            max[axis] = max[axis].max(point[axis]);
+        }
+    }
+    let extent = (max[0] - min[0]).max(max[1] - min[1]).max(f32::EPSILON);
+    let scale = full_size as f32 * (1.0 - MARGIN * 2.0) / extent;
+    let center = [(min[0] + max[0]) / 2.0, (min[1] + max[1]) / 2.0];
+    let half = full_size as f32 / 2.0;
+    let screen: Vec<[f32; 3]> = points.iter()
+        .map(|p| [half + (p[0] - center[0]) * scale, half - (p[1] - center[1]) * scale, p[2]])
+        .collect();
+
+    let light = normalize(LIGHT);
+    let colors = mesh.has_colors();
+    let mut depth = vec![f32::NEG_INFINITY; (full_size * full_size) as usize];
+
+    for face in faces {
+        let [a, b, c] = face.map(|i| screen[i as usize]);
+        let [pa, pb, pc] = face.map(|i| points[i as usize]);
+
+        // Flat shading, lit from both sides as meshes aren't always closed
+        let normal = normalize(cross(
+            [pb[0] - pa[0], pb[1] - pa[1], pb[2] - pa[2]],
+            [pc[0] - pa[0], pc[1] - pa[1], pc[2] - pa[2]],
+        ));
+        let light_amount = (normal[0] * light[0] + normal[1] * light[1] + normal[2] * light[2]).abs();
+        let shade = AMBIENT + (1.0 - AMBIENT) * light_amount;
+        let base = if colors {
+            let sum = face.iter().fold([0.0; 3], |sum, i| {
+                let color = mesh.vertices[*i as usize].color;
+                std::array::from_fn(|c| sum[c] + color[c] as f32 / 255.0 / 3.0)
+            });
+            sum
+        } else {
+            BASE_COLOR
+        };
+        let pixel = Rgba([
+            (base[0] * shade * 255.0) as u8,
+            (base[1] * shade * 255.0) as u8,
+            (base[2] * shade * 255.0) as u8,
+            255,
+        ]);
+
+        let area = (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0]);
+        if area.abs() < f32::EPSILON {
+            continue;
+        }
+        let left = a[0].min(b[0]).min(c[0]).floor().max(0.0) as u32;
+        let right = (a[0].max(b[0]).max(c[0]).ceil() as u32).min(full_size - 1);
+        let top = a[1].min(b[1]).min(c[1]).floor().max(0.0) as u32;
+        let bottom = (a[1].max(b[1]).max(c[1]).ceil() as u32).min(full_size - 1);
+
+        for y in top..=bottom {
+            for x in left..=right {
+                let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+                // Barycentric weights, all of them have the sign of the area inside of the triangle
+                let wa = ((b[0] - px) * (c[1] - py) - (b[1] - py) * (c[0] - px)) / area;
+                let wb = ((c[0] - px) * (a[1] - py) - (c[1] - py) * (a[0] - px)) / area;
+                let wc = 1.0 - wa - wb;
+                if wa < 0.0 || wb < 0.0 || wc < 0.0 {
+                    continue;
+                }
+                let z = wa * a[2] + wb * b[2] + wc * c[2];
+                let index = (y * full_size + x) as usize;
+                if z > depth[index] {
+                    depth[index] = z;
+                    image.put_pixel(x, y, pixel);
+                }
+            }
+        }
+    }
+
+    imageops::resize(&image, size.max(1), size.max(1), imageops::FilterType::Triangle)
+}
@@ -0,0 +1,101 @@
+// What refreshing found out about every cached file, so the next refresh only has to
+// open files that were added or changed since.
+//
+// Files are looked up by name and only trusted while their size and modification time
+// are unchanged. Each tab keeps its own entries, which are thrown away when the tab is
+// read from another directory or its signatures change.
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use super::AssetInfo;
+
+// Bump when what is stored or how it is hashed changes, older indexes are ignored
+const VERSION: u32 = 2;
+
+#[derive(Serialize, Deserialize)]
+struct ScanEntry {
+    size: u64,
+    modified: Option<SystemTime>,
+    /// None when the file doesn't belong in this tab
+    asset: Option<AssetInfo>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct TabIndex {
+    directory: PathBuf,
+    signatures: u64,
+    entries: HashMap<String, ScanEntry>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct ScanIndex {
+    version: u32,
+    tabs: HashMap<String, TabIndex>,
+    #[serde(skip)]
+    changed: bool,
+}
+
+impl ScanIndex {
+    /// Read the index from a file, an empty index is used if it doesn't exist yet or is outdated
+    pub fn load(path: &Path) -> ScanIndex {
+        let index: ScanIndex = match fs::read(path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_default(),
+            Err(_) => ScanIndex::default(),
+        };
+        if index.version == VERSION {
+            index
+        } else {
+            ScanIndex { version: VERSION, ..Default::default() }
+        }
+    }
+
+    /// Only writes the file if something changed since it was loaded or last saved
+    pub fn save(&mut self, path: &Path) -> std::io::Result<()> {
+        if !self.changed {
+            return Ok(())
+        }
+        fs::write(path, serde_json::to_vec(self)?)?;
+        self.changed = false;
+        Ok(())
+    }
+
+    /// Forget the entries of a tab if they were made for another directory or other signatures
+    pub fn prepare(&mut self, mode: &str, directory: &Path, signatures: u64) {
+        let tab = self.tabs.entry(mode.to_owned()).or_default();
+        if tab.directory != directory || tab.signatures != signatures {
+            *tab = TabIndex {
+                directory: directory.to_owned(),
+                signatures,
+                entries: HashMap::new(),
+            };
+            self.changed = true;
+        }
+    }
+
+    /// What was found when the file was last read, as long as its size and modification time are unchanged.
+    /// The inner None means the file doesn't belong in the tab.
+    pub fn get(&self, mode: &str, name: &str, size: u64, modified: Option<SystemTime>) -> Option<Option<&AssetInfo>> {
+        self.tabs.get(mode)?.entries.get(name)
+            .filter(|entry| entry.size == size && entry.modified == modified)
+            .map(|entry| entry.asset.as_ref())
+    }
+
+    pub fn insert(&mut self, mode: &str, name: &str, size: u64, modified: Option<SystemTime>, asset: Option<AssetInfo>) {
+        let tab = self.tabs.entry(mode.to_owned()).or_default();
+        tab.entries.insert(name.to_owned(), ScanEntry { size, modified, asset });
+        self.changed = true;
+    }
+
+    /// Forget files that are no longer cached
+    pub fn retain(&mut self, mode: &str, keep: impl Fn(&str) -> bool) {
+        if let Some(tab) = self.tabs.get_mut(mode) {
+            let before = tab.entries.len();
+            tab.entries.retain(|name, _| keep(name));
+            self.changed |= tab.entries.len() != before;
+        }
+    }
+}
@@ -0,0 +1,240 @@
+use std::{fs, sync::Mutex};
+use lazy_static::lazy_static;
+use serde::Deserialize;
+
+use crate::{config, log, logic};
+
+lazy_static! {
+    // Built-in signatures followed by the ones from the user's signature file
+    static ref SIGNATURES: Mutex<Vec<Signature>> = Mutex::new(load_signatures());
+}
+
+/// A file signature used to categorise and extract cached assets.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Signature {
+    /// Name of the signature, e.g. "PNG"
+    pub name: String,
+    /// Category (tab) the signature belongs to, e.g. "images"
+    pub category: String,
+    /// Bytes that identify the file, written as a string or an array of bytes in the signature file
+    #[serde(default, deserialize_with = "deserialize_magic")]
+    pub magic: Vec<u8>,
+    /// Position of the magic bytes from the start of the real file, it is subtracted from the found magic when extracting
+    #[serde(default)]
+    pub offset: usize,
+    /// File extension added when extracting, without the dot
+    pub extension: String,
+    /// Extra byte strings that also have to be inside of the file for it to match
+    #[serde(default)]
+    pub requires: Vec<String>,
+    /// Content types stored with the cache entry that identify the file, checked before the magic bytes
+    #[serde(default)]
+    pub content_types: Vec<String>,
+    /// Only match the magic bytes at the offset from the start instead of anywhere, for magic that is too short to scan for
+    #[serde(default)]
+    pub anchored: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Magic {
+    Text(String),
+    Bytes(Vec<u8>),
+}
+
+fn deserialize_magic<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    match Magic::deserialize(deserializer)? {
+        Magic::Text(text) => Ok(text.into_bytes()),
+        Magic::Bytes(bytes) => Ok(bytes),
+    }
+}
+
+fn bytes_contains(haystack: &[u8], needle: &[u8]) -> bool {
+    let len = needle.len();
+    if len > 0 {
+        haystack.windows(len).any(|window| window == needle)
+    } else {
+        false
+    }
+}
+
+impl Signature {
+    fn new(name: &str, category: &str, magic: &[u8], offset: usize, extension: &str) -> Self {
+        Signature {
+            name: name.to_owned(),
+            category: category.to_owned(),
+            magic: magic.to_vec(),
+            offset,
+            extension: extension.to_owned(),
+            requires: Vec::new(),
+            content_types: Vec::new(),
+            anchored: false,
+        }
+    }
+
+    fn anchored(mut self) -> Self {
+        self.anchored = true;
+        self
+    }
+
+    fn with_requires(mut self, requires: &[&str]) -> Self {
+        self.requires = requires.iter().map(|s| s.to_string()).collect();
+        self
+    }
+
+    fn with_content_types(mut self, content_types: &[&str]) -> Self {
+        self.content_types = content_types.iter().map(|s| s.to_string()).collect();
+        self
+    }
+
+    fn magic_at_offset(&self, bytes: &[u8]) -> bool {
+        match bytes.get(self.offset..) {
+            Some(bytes) => !self.magic.is_empty() && bytes.starts_with(&self.magic),
+            None => false,
+        }
+    }
+
+    /// Check if the magic bytes and every extra check are inside of the bytes
+    pub fn matches(&self, bytes: &[u8]) -> bool {
+        let magic_found = if self.anchored {
+            self.magic_at_offset(bytes)
+        } else {
+            bytes_contains(bytes, &self.magic)
+        };
+        magic_found && self.requires.iter().all(|needle| bytes_contains(bytes, needle.as_bytes()))
+    }
+
+    /// Check if the body of a cache entry starts with the magic bytes, extra checks can also be in the response headers
+    pub fn matches_body(&self, headers: &[u8], body: &[u8]) -> bool {
+        self.magic_at_offset(body) && self.requires.iter().all(|needle| {
+            bytes_contains(headers, needle.as_bytes()) || bytes_contains(body, needle.as_bytes())
+        })
+    }
+
+    /// Find where the real file starts within the bytes
+    pub fn find_start(&self, bytes: &[u8]) -> Option<usize> {
+        let len = self.magic.len();
+        if len == 0 {
+            return None
+        }
+        if self.anchored {
+            return self.magic_at_offset(bytes).then_some(0)
+        }
+        let index = bytes.windows(len).position(|window| window == self.magic.as_slice())?;
+        Some(index.saturating_sub(self.offset))
+    }
+}
+
+fn default_signatures() -> Vec<Signature> {
+    vec![
+        Signature::new("OggS", "sounds", b"OggS", 0, "ogg")
+            .with_content_types(&["audio/ogg", "application/ogg"]),
+        // Only allow mp3 if type is binary as that is what the client uses
+        Signature::new("ID3", "sounds", b"ID3", 0, "mp3")
+            .with_requires(&["binary/"]),
+        // Mp3 files without an ID3 tag start with a frame, which is too short to scan for
+        Signature::new("MP3", "sounds", b"", 0, "mp3")
+            .with_content_types(&["audio/mpeg", "audio/mp3"]),
+        Signature::new("WAV", "sounds", b"WAVE", 8, "wav")
+            .with_content_types(&["audio/wav", "audio/wave", "audio/x-wav"]),
+        Signature::new("FLAC", "sounds", b"fLaC", 0, "flac")
+            .with_content_types(&["audio/flac", "audio/x-flac"]),
+        Signature::new("PNG", "images", b"PNG", 1, "png")
+            .with_content_types(&["image/png"]),
+        Signature::new("WEBP", "images", b"WEBP", 8, "webp")
+            .with_content_types(&["image/webp"]),
+        // JPEG and BMP magic is short enough to show up inside of other files
+        Signature::new("JPEG", "images", b"\xFF\xD8\xFF", 0, "jpg")
+            .anchored()
+            .with_content_types(&["image/jpeg", "image/jpg"]),
+        Signature::new("GIF", "images", b"GIF8", 0, "gif")
+            .with_content_types(&["image/gif"]),
+        Signature::new("DDS", "images", b"DDS ", 0, "dds")
+            .with_content_types(&["image/vnd-ms.dds", "image/x-dds", "image/dds"]),
+        Signature::new("BMP", "images", b"BM", 0, "bmp")
+            .anchored()
+            .with_content_types(&["image/bmp", "image/x-bmp", "image/x-ms-bmp"]),
+        // TGA has no magic bytes at all
+        Signature::new("TGA", "images", b"", 0, "tga")
+            .with_content_types(&["image/x-tga", "image/tga", "image/x-targa"]),
+        Signature::new("<roblox!", "rbxm-files", b"<roblox!", 0, "rbxm"),
+        // Meshes start with their version as text, 1.00 to 5.00
+        Signature::new("Mesh v1", "meshes", b"version 1.0", 0, "mesh"),
+        Signature::new("Mesh v2", "meshes", b"version 2.00", 0, "mesh"),
+        Signature::new("Mesh v3", "meshes", b"version 3.0", 0, "mesh"),
+        Signature::new("Mesh v4", "meshes", b"version 4.0", 0, "mesh"),
+        Signature::new("Mesh v5", "meshes", b"version 5.00", 0, "mesh"),
+        Signature::new("KTX", "ktx-files", b"KTX 11", 1, "ktx")
+            .with_content_types(&["image/ktx"]),
+        Signature::new("KTX2", "ktx-files", b"KTX 20", 1, "ktx2")
+            .with_content_types(&["image/ktx2"]),
+        Signature::new("WEBM", "videos", b"\x1A\x45\xDF\xA3", 0, "webm")
+            .with_content_types(&["video/webm"]),
+    ]
+}
+
+fn read_signature_file(path: &str) -> Vec<Signature> {
+    let path = logic::resolve_path(path);
+    match fs::read(&path) {
+        Ok(bytes) => {
+            match serde_json::from_slice::<Vec<Signature>>(&bytes) {
+                Ok(signatures) => {
+                    log::info(&format!("Loaded {} signatures from {}", signatures.len(), path));
+                    return signatures
+                }
+                Err(e) => log::warn(&format!("Failed to parse signature file! {}", e)),
+            }
+        }
+        Err(e) => log::warn(&format!("Failed to read signature file '{}': {}", path, e)),
+    }
+    Vec::new()
+}
+
+fn load_signatures() -> Vec<Signature> {
+    let mut signatures = default_signatures();
+
+    if let Some(path) = config::get_config_string("signatures_file") {
+        for signature in read_signature_file(&path) {
+            if signature.magic.is_empty() && signature.content_types.is_empty() {
+                log::warn(&format!("Ignoring signature '{}' as it has no magic bytes or content types", signature.name));
+                continue;
+            }
+            // User signatures replace built-in ones with the same name
+            if let Some(existing) = signatures.iter_mut().find(|s| s.name == signature.name) {
+                *existing = signature;
+            } else {
+                signatures.push(signature);
+            }
+        }
+    }
+
+    signatures
+}
+
+pub fn get_signatures() -> Vec<Signature> {
+    SIGNATURES.lock().unwrap().clone()
+}
+
+pub fn get_category_signatures(category: &str) -> Vec<Signature> {
+    get_signatures().into_iter().filter(|s| s.category == category).collect()
+}
+
+/// Categories in the order they first appear in the registry
+pub fn get_categories() -> Vec<String> {
+    let mut categories: Vec<String> = Vec::new();
+    for signature in get_signatures() {
+        if !categories.contains(&signature.category) {
+            categories.push(signature.category);
+        }
+    }
+    categories
+}
+
+/// Find the first signature that lists the content type, parameters such as charset are ignored
+pub fn find_content_type(content_type: &str) -> Option<Signature> {
+    let mime = content_type.split(';').next().unwrap_or("").trim().to_lowercase();
+    if mime.is_empty() {
+        return None
+    }
+    get_signatures().into_iter().find(|s| s.content_types.iter().any(|c| c.to_lowercase() == mime))
+}
@@ -0,0 +1,145 @@
+// Size and modification time of every file in the cache at one moment
+//
+// Used to capture a play session: a snapshot is taken when the capture starts, and the
+// files that are missing from it or have changed since are the ones the game downloaded.
+// Named snapshots also store the category and a hash of every asset, so two of them can
+// be compared to find the assets that were added, removed or modified in between.
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use super::stable_hash;
+
+// Directories in the cache that hold assets
+pub const DIRECTORIES: [&str; 2] = ["http", "sounds"];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct FileStamp {
+    size: u64,
+    modified: Option<SystemTime>,
+    /// Only stored in named snapshots, None for files that aren't assets
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    category: Option<String>,
+    /// Hash of the extracted asset, only stored in named snapshots
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hash: Option<u64>,
+}
+
+impl FileStamp {
+    /// Assets with a hash are compared by their contents, as the cache can rewrite them without changing them.
+    /// Hashes are only compared when both snapshots made them the same way.
+    fn is_modified(&self, newer: &FileStamp, same_hashes: bool) -> bool {
+        match (self.hash, newer.hash) {
+            (Some(hash), Some(newer_hash)) if same_hashes => hash != newer_hash,
+            _ => self.size != newer.size || self.modified != newer.modified,
+        }
+    }
+}
+
+/// Assets that differ between two snapshots in one category
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CategoryDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub modified: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub taken: SystemTime,
+    /// How the asset hashes were made, None for snapshots saved before it was stored
+    #[serde(default)]
+    hash_algorithm: Option<String>,
+    /// Files by directory, then by name
+    directories: HashMap<String, HashMap<String, FileStamp>>,
+}
+
+impl Snapshot {
+    pub fn take(cache_directory: &Path) -> Snapshot {
+        let directories = DIRECTORIES.iter().map(|directory| {
+            let files = match fs::read_dir(cache_directory.join(directory)) {
+                Ok(entries) => entries.flatten().filter_map(|entry| {
+                    let metadata = entry.metadata().ok()?;
+                    let stamp = FileStamp { size: metadata.len(), modified: metadata.modified().ok(), category: None, hash: None };
+                    Some((entry.file_name().to_string_lossy().to_string(), stamp))
+                }).collect(),
+                Err(_) => HashMap::new(), // The directory doesn't exist yet
+            };
+            (directory.to_string(), files)
+        }).collect();
+
+        Snapshot { taken: SystemTime::now(), hash_algorithm: Some(stable_hash::ALGORITHM.to_owned()), directories }
+    }
+
+    pub fn load(path: &Path) -> std::io::Result<Snapshot> {
+        Ok(serde_json::from_slice(&fs::read(path)?)?)
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        fs::write(path, serde_json::to_vec(self)?)
+    }
+
+    /// Whether a file was added or has changed since the snapshot was taken
+    pub fn is_changed(&self, directory: &str, name: &str, size: u64, modified: Option<SystemTime>) -> bool {
+        self.directories.get(directory)
+            .and_then(|files| files.get(name))
+            .is_none_or(|stamp| stamp.size != size || stamp.modified != modified)
+    }
+
+    /// Every file with the directory it is in, to be classified for a named snapshot
+    pub fn files(&self) -> Vec<(String, String)> {
+        self.directories.iter().flat_map(|(directory, files)| {
+            files.keys().map(move |name| (directory.clone(), name.clone()))
+        }).collect()
+    }
+
+    pub fn set_asset(&mut self, directory: &str, name: &str, category: Option<String>, hash: Option<u64>) {
+        if let Some(stamp) = self.directories.get_mut(directory).and_then(|files| files.get_mut(name)) {
+            stamp.category = category;
+            stamp.hash = hash;
+        }
+    }
+
+    /// Assets that were added, removed or modified between this snapshot and a newer one, by category.
+    /// Assets that moved to another category are modified in their new category.
+    pub fn diff(&self, newer: &Snapshot) -> BTreeMap<String, CategoryDiff> {
+        let mut diff: BTreeMap<String, CategoryDiff> = BTreeMap::new();
+        let empty = HashMap::new();
+        let same_hashes = self.hash_algorithm.is_some() && self.hash_algorithm == newer.hash_algorithm;
+
+        for directory in DIRECTORIES {
+            let old_files = self.directories.get(directory).unwrap_or(&empty);
+            let new_files = newer.directories.get(directory).unwrap_or(&empty);
+
+            for (name, stamp) in new_files {
+                let Some(category) = &stamp.category else { continue };
+                match old_files.get(name) {
+                    None => diff.entry(category.clone()).or_default().added.push(name.clone()),
+                    Some(old) if old.category.as_ref() != Some(category) || old.is_modified(stamp, same_hashes) => {
+                        diff.entry(category.clone()).or_default().modified.push(name.clone())
+                    }
+                    Some(_) => (),
+                }
+            }
+            for (name, stamp) in old_files {
+                if let Some(category) = &stamp.category {
+                    // Deleted, or it isn't an asset anymore
+                    if new_files.get(name).is_none_or(|new| new.category.is_none()) {
+                        diff.entry(category.clone()).or_default().removed.push(name.clone());
+                    }
+                }
+            }
+        }
+
+        // The order of the files isn't kept, so the same snapshots always give the same diff
+        for category in diff.values_mut() {
+            category.added.sort();
+            category.removed.sort();
+            category.modified.sort();
+        }
+        diff
+    }
+}
@@ -0,0 +1,26 @@
+// 64 bit FNV-1a, for hashes that are saved to disk
+//
+// The standard library's hasher is allowed to change between Rust releases, so hashes
+// saved by one build of the program might not match the ones made by the next.
+// FNV-1a is specified byte by byte and always gives the same hash for the same bytes.
+const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const PRIME: u64 = 0x100000001b3;
+
+/// Saved next to hashes, so they are only compared with hashes made the same way
+pub const ALGORITHM: &str = "fnv1a-64";
+
+pub fn hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(OFFSET_BASIS, |hash, byte| (hash ^ *byte as u64).wrapping_mul(PRIME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_reference_values() {
+        assert_eq!(hash(b""), 0xcbf29ce484222325);
+        assert_eq!(hash(b"a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(hash(b"foobar"), 0x85944171f73967e8);
+    }
+}
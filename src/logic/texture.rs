@@ -0,0 +1,236 @@
+// Decoding of the GPU texture formats found inside of KTX and KTX2 files
+use std::io::{Error, ErrorKind, Result};
+
+use image::RgbaImage;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TextureFormat {
+    R8,
+    Rg8,
+    Rgb8,
+    Rgba8,
+    Etc1,
+    Etc2Rgb,
+    Etc2Rgba1,
+    Etc2Rgba8,
+    EacR11,
+    EacRg11,
+    Bc1,
+    Bc2,
+    Bc3,
+    Bc4,
+    Bc5,
+    Bc6h,
+    Bc7,
+    Astc(u8, u8),
+}
+
+// Largest width or height accepted from a header, textures used in game are far smaller
+pub const MAX_DIMENSION: u32 = 16384;
+
+// OpenGL enums used by KTX1
+const GL_UNSIGNED_BYTE: u32 = 0x1401;
+const GL_RED: u32 = 0x1903;
+const GL_RGB: u32 = 0x1907;
+const GL_RGBA: u32 = 0x1908;
+const GL_LUMINANCE: u32 = 0x1909;
+const GL_LUMINANCE_ALPHA: u32 = 0x190A;
+const GL_RG: u32 = 0x8227;
+
+// Block sizes of the ASTC formats, in the same order as their OpenGL and Vulkan enums
+const ASTC_BLOCKS: [(u8, u8); 14] = [
+    (4, 4), (5, 4), (5, 5), (6, 5), (6, 6), (8, 5), (8, 6),
+    (8, 8), (10, 5), (10, 6), (10, 8), (10, 10), (12, 10), (12, 12),
+];
+
+impl TextureFormat {
+    /// Get the format from the glType, glFormat and glInternalFormat fields of a KTX1 header
+    pub fn from_gl(gl_type: u32, gl_format: u32, gl_internal_format: u32) -> Option<TextureFormat> {
+        if gl_type == GL_UNSIGNED_BYTE {
+            // Uncompressed, glFormat describes the channels
+            return match gl_format {
+                GL_RED | GL_LUMINANCE => Some(TextureFormat::R8),
+                GL_RG | GL_LUMINANCE_ALPHA => Some(TextureFormat::Rg8),
+                GL_RGB => Some(TextureFormat::Rgb8),
+                GL_RGBA => Some(TextureFormat::Rgba8),
+                _ => None,
+            }
+        }
+
+        match gl_internal_format {
+            0x8D64 => Some(TextureFormat::Etc1),
+            0x9274 | 0x9275 => Some(TextureFormat::Etc2Rgb),
+            0x9276 | 0x9277 => Some(TextureFormat::Etc2Rgba1),
+            0x9278 | 0x9279 => Some(TextureFormat::Etc2Rgba8),
+            0x9270 | 0x9271 => Some(TextureFormat::EacR11),
+            0x9272 | 0x9273 => Some(TextureFormat::EacRg11),
+            0x83F0 | 0x83F1 | 0x8C4C | 0x8C4D => Some(TextureFormat::Bc1),
+            0x83F2 | 0x8C4E => Some(TextureFormat::Bc2),
+            0x83F3 | 0x8C4F => Some(TextureFormat::Bc3),
+            0x8DBB | 0x8DBC => Some(TextureFormat::Bc4),
+            0x8DBD | 0x8DBE => Some(TextureFormat::Bc5),
+            0x8E8E | 0x8E8F => Some(TextureFormat::Bc6h),
+            0x8E8C | 0x8E8D => Some(TextureFormat::Bc7),
+            0x93B0..=0x93BD => Some(TextureFormat::astc(ASTC_BLOCKS[(gl_internal_format - 0x93B0) as usize])),
+            0x93D0..=0x93DD => Some(TextureFormat::astc(ASTC_BLOCKS[(gl_internal_format - 0x93D0) as usize])),
+            _ => None,
+        }
+    }
+
+    /// Get the format from the vkFormat field of a KTX2 header, UNORM and SRGB variants decode the same
+    pub fn from_vk(vk_format: u32) -> Option<TextureFormat> {
+        match vk_format {
+            9 | 15 => Some(TextureFormat::R8),
+            16 | 22 => Some(TextureFormat::Rg8),
+            23 | 29 => Some(TextureFormat::Rgb8),
+            37 | 43 => Some(TextureFormat::Rgba8),
+            131..=134 => Some(TextureFormat::Bc1),
+            135 | 136 => Some(TextureFormat::Bc2),
+            137 | 138 => Some(TextureFormat::Bc3),
+            139 | 140 => Some(TextureFormat::Bc4),
+            141 | 142 => Some(TextureFormat::Bc5),
+            143 | 144 => Some(TextureFormat::Bc6h),
+            145 | 146 => Some(TextureFormat::Bc7),
+            147 | 148 => Some(TextureFormat::Etc2Rgb),
+            149 | 150 => Some(TextureFormat::Etc2Rgba1),
+            151 | 152 => Some(TextureFormat::Etc2Rgba8),
+            153 | 154 => Some(TextureFormat::EacR11),
+            155 | 156 => Some(TextureFormat::EacRg11),
+            157..=184 => Some(TextureFormat::astc(ASTC_BLOCKS[((vk_format - 157) / 2) as usize])),
+            _ => None,
+        }
+    }
+
+    fn astc(block: (u8, u8)) -> TextureFormat {
+        TextureFormat::Astc(block.0, block.1)
+    }
+
+    /// Short name of the format, shown with the other details of a texture
+    pub fn name(&self) -> String {
+        match self {
+            TextureFormat::R8 => "R8".to_owned(),
+            TextureFormat::Rg8 => "RG8".to_owned(),
+            TextureFormat::Rgb8 => "RGB8".to_owned(),
+            TextureFormat::Rgba8 => "RGBA8".to_owned(),
+            TextureFormat::Etc1 => "ETC1".to_owned(),
+            TextureFormat::Etc2Rgb => "ETC2".to_owned(),
+            TextureFormat::Etc2Rgba1 => "ETC2A1".to_owned(),
+            TextureFormat::Etc2Rgba8 => "ETC2A8".to_owned(),
+            TextureFormat::EacR11 => "EAC_R11".to_owned(),
+            TextureFormat::EacRg11 => "EAC_RG11".to_owned(),
+            TextureFormat::Bc1 => "BC1".to_owned(),
+            TextureFormat::Bc2 => "BC2".to_owned(),
+            TextureFormat::Bc3 => "BC3".to_owned(),
+            TextureFormat::Bc4 => "BC4".to_owned(),
+            TextureFormat::Bc5 => "BC5".to_owned(),
+            TextureFormat::Bc6h => "BC6H".to_owned(),
+            TextureFormat::Bc7 => "BC7".to_owned(),
+            TextureFormat::Astc(width, height) => format!("ASTC{}x{}", width, height),
+        }
+    }
+
+    /// Whether the format stores an alpha channel. BC1 is left out as the
+    /// header doesn't say if its punch-through alpha is used
+    pub fn has_alpha(&self) -> bool {
+        matches!(self,
+            TextureFormat::Rgba8 | TextureFormat::Etc2Rgba1 | TextureFormat::Etc2Rgba8
+            | TextureFormat::Bc2 | TextureFormat::Bc3 | TextureFormat::Bc7 | TextureFormat::Astc(..))
+    }
+
+    /// Block width, block height and bytes per block, uncompressed formats use 1x1 blocks
+    pub fn block_size(&self) -> (usize, usize, usize) {
+        match self {
+            TextureFormat::R8 => (1, 1, 1),
+            TextureFormat::Rg8 => (1, 1, 2),
+            TextureFormat::Rgb8 => (1, 1, 3),
+            TextureFormat::Rgba8 => (1, 1, 4),
+            TextureFormat::Etc1 | TextureFormat::Etc2Rgb | TextureFormat::Etc2Rgba1
+            | TextureFormat::EacR11 | TextureFormat::Bc1 | TextureFormat::Bc4 => (4, 4, 8),
+            TextureFormat::Etc2Rgba8 | TextureFormat::EacRg11 | TextureFormat::Bc2 | TextureFormat::Bc3
+            | TextureFormat::Bc5 | TextureFormat::Bc6h | TextureFormat::Bc7 => (4, 4, 16),
+            TextureFormat::Astc(width, height) => (*width as usize, *height as usize, 16),
+        }
+    }
+
+    /// Size of an image in this format in bytes, rows of uncompressed images are padded to 4 bytes if aligned is set.
+    /// None if it doesn't fit in memory
+    pub fn image_size(&self, width: u32, height: u32, aligned: bool) -> Option<usize> {
+        let (block_width, block_height, block_bytes) = self.block_size();
+        let blocks_x = (width as usize).div_ceil(block_width);
+        let blocks_y = (height as usize).div_ceil(block_height);
+        let row = blocks_x.checked_mul(block_bytes)?;
+        if aligned && block_width == 1 {
+            row.checked_next_multiple_of(4)?.checked_mul(blocks_y)
+        } else {
+            row.checked_mul(blocks_y)
+        }
+    }
+}
+
+fn bgra_to_image(pixels: &[u32], width: u32, height: u32) -> RgbaImage {
+    let mut bytes = Vec::with_capacity(pixels.len() * 4);
+    for pixel in pixels {
+        let [b, g, r, a] = pixel.to_le_bytes();
+        bytes.extend_from_slice(&[r, g, b, a]);
+    }
+    RgbaImage::from_raw(width, height, bytes).expect("Pixel buffer has the wrong size")
+}
+
+fn decode_uncompressed(format: TextureFormat, data: &[u8], width: u32, height: u32, aligned: bool) -> RgbaImage {
+    let channels = format.block_size().2;
+    let row_len = width as usize * channels;
+    let stride = if aligned { row_len.next_multiple_of(4) } else { row_len };
+
+    let mut image = RgbaImage::new(width, height);
+    for (y, row) in data.chunks(stride).take(height as usize).enumerate() {
+        for (x, pixel) in row[..row_len.min(row.len())].chunks_exact(channels).enumerate() {
+            let rgba = match pixel {
+                [r] => [*r, *r, *r, 255],
+                [r, g] => [*r, *g, 0, 255],
+                [r, g, b] => [*r, *g, *b, 255],
+                [r, g, b, a] => [*r, *g, *b, *a],
+                _ => unreachable!(),
+            };
+            image.put_pixel(x as u32, y as u32, image::Rgba(rgba));
+        }
+    }
+    image
+}
+
+/// Decode an image to RGBA, aligned is set when rows of uncompressed images are padded to 4 bytes (KTX1)
+pub fn decode(format: TextureFormat, data: &[u8], width: u32, height: u32, aligned: bool) -> Result<RgbaImage> {
+    let expected = format.image_size(width, height, aligned)
+        .ok_or_else(|| Error::new(ErrorKind::InvalidData, "Texture is too large"))?;
+    if data.len() < expected {
+        return Err(Error::new(ErrorKind::UnexpectedEof, format!("Texture data is too short, expected {} bytes but got {}", expected, data.len())));
+    }
+
+    if format.block_size().0 == 1 {
+        return Ok(decode_uncompressed(format, data, width, height, aligned))
+    }
+
+    let (w, h) = (width as usize, height as usize);
+    let mut pixels = vec![0u32; w * h];
+    let result = match format {
+        TextureFormat::Etc1 => texture2ddecoder::decode_etc1(data, w, h, &mut pixels),
+        TextureFormat::Etc2Rgb => texture2ddecoder::decode_etc2_rgb(data, w, h, &mut pixels),
+        TextureFormat::Etc2Rgba1 => texture2ddecoder::decode_etc2_rgba1(data, w, h, &mut pixels),
+        TextureFormat::Etc2Rgba8 => texture2ddecoder::decode_etc2_rgba8(data, w, h, &mut pixels),
+        TextureFormat::EacR11 => texture2ddecoder::decode_eacr(data, w, h, &mut pixels),
+        TextureFormat::EacRg11 => texture2ddecoder::decode_eacrg(data, w, h, &mut pixels),
+        TextureFormat::Bc1 => texture2ddecoder::decode_bc1a(data, w, h, &mut pixels),
+        TextureFormat::Bc2 => texture2ddecoder::decode_bc2(data, w, h, &mut pixels),
+        TextureFormat::Bc3 => texture2ddecoder::decode_bc3(data, w, h, &mut pixels),
+        TextureFormat::Bc4 => texture2ddecoder::decode_bc4(data, w, h, &mut pixels),
+        TextureFormat::Bc5 => texture2ddecoder::decode_bc5(data, w, h, &mut pixels),
+        TextureFormat::Bc6h => texture2ddecoder::decode_bc6_unsigned(data, w, h, &mut pixels),
+        TextureFormat::Bc7 => texture2ddecoder::decode_bc7(data, w, h, &mut pixels),
+        TextureFormat::Astc(block_width, block_height) => {
+            texture2ddecoder::decode_astc(data, w, h, block_width as usize, block_height as usize, &mut pixels)
+        }
+        _ => unreachable!("Uncompressed formats are decoded above"),
+    };
+    result.map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+
+    Ok(bgra_to_image(&pixels, width, height))
+}
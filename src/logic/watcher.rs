@@ -0,0 +1,64 @@
+// Watches the cache directories while Roblox is running, so the list changes without refreshing
+//
+// Roblox writes cache entries in several steps, so events are collected until the directories
+// have been quiet for a moment, then every file that changed is handed over once. Files are
+// also handed over when Roblox keeps writing for a while, so the list doesn't fall behind.
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use notify::{EventKind, RecursiveMode, Watcher};
+
+use crate::log;
+
+const QUIET_TIME: Duration = Duration::from_millis(300);
+const MAX_WAIT: Duration = Duration::from_secs(2);
+
+/// Stops watching when dropped
+pub struct CacheWatcher {
+    _watcher: notify::RecommendedWatcher,
+}
+
+impl CacheWatcher {
+    /// Start watching the directories, `changed` is called on another thread with the files that were
+    /// created, changed or deleted
+    pub fn new(directories: &[PathBuf], changed: impl Fn(Vec<PathBuf>) + Send + 'static) -> notify::Result<CacheWatcher> {
+        let (sender, receiver) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender)?;
+        for directory in directories {
+            watcher.watch(directory, RecursiveMode::NonRecursive)?;
+        }
+
+        thread::spawn(move || {
+            let mut pending = BTreeSet::new();
+            let mut waiting_since = None;
+            loop {
+                let quiet = match receiver.recv_timeout(QUIET_TIME) {
+                    Ok(Ok(event)) => {
+                        // Reading files doesn't change them
+                        if !matches!(event.kind, EventKind::Access(_)) {
+                            pending.extend(event.paths);
+                            waiting_since.get_or_insert_with(Instant::now);
+                        }
+                        false
+                    }
+                    Ok(Err(e)) => {
+                        log::warn(&format!("Failed to watch the cache: {}", e));
+                        false
+                    }
+                    Err(RecvTimeoutError::Timeout) => true,
+                    Err(RecvTimeoutError::Disconnected) => break, // The watcher was dropped
+                };
+
+                if waiting_since.is_some_and(|since: Instant| quiet || since.elapsed() >= MAX_WAIT) {
+                    changed(std::mem::take(&mut pending).into_iter().collect());
+                    waiting_since = None;
+                }
+            }
+        });
+
+        Ok(CacheWatcher { _watcher: watcher })
+    }
+}
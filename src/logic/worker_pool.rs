@@ -0,0 +1,151 @@
+// Runs slow per file work (sniffing, hashing, extracting) on several threads at once
+//
+// Workers take the next item from a shared counter and send back what they found. The
+// calling thread hands the results over in the same order as the items, so file lists,
+// progress and status messages come out the same no matter which worker finished first.
+// Workers don't run further ahead than a few items per thread, which keeps the results
+// that are waiting for a slow item from piling up.
+use std::collections::BTreeMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{mpsc, Condvar, Mutex};
+use std::thread;
+
+// How many items each worker may be ahead of the oldest result that wasn't handed over yet
+const ITEMS_AHEAD_PER_WORKER: usize = 4;
+
+struct Queue {
+    next: usize,
+    handed_over: usize,
+    stop: bool,
+}
+
+/// Amount of workers to use when the user hasn't chosen one
+pub fn default_workers() -> usize {
+    thread::available_parallelism().map_or(1, |threads| threads.get())
+}
+
+/// Run `work` on every item with up to `workers` threads. `done` gets the index and result of
+/// each item in order, and can return false to stop, which makes this return false as well.
+/// If `work` panics the other workers are stopped and the panic continues on the calling thread.
+pub fn run<T, R>(
+    items: &[T],
+    workers: usize,
+    work: impl Fn(&T) -> R + Sync,
+    mut done: impl FnMut(usize, R) -> bool,
+) -> bool
+where
+    T: Sync,
+    R: Send,
+{
+    let workers = workers.clamp(1, items.len().max(1));
+    let window = workers * ITEMS_AHEAD_PER_WORKER;
+    let queue = Mutex::new(Queue { next: 0, handed_over: 0, stop: false });
+    let queue_changed = Condvar::new();
+
+    thread::scope(|scope| {
+        let (sender, receiver) = mpsc::channel();
+        for _ in 0..workers {
+            let sender = sender.clone();
+            let (queue, queue_changed, work) = (&queue, &queue_changed, &work);
+            scope.spawn(move || loop {
+                let index = {
+                    let mut queue = queue.lock().unwrap();
+                    while !queue.stop && queue.next < items.len() && queue.next >= queue.handed_over + window {
+                        queue = queue_changed.wait(queue).unwrap();
+                    }
+                    if queue.stop || queue.next >= items.len() {
+                        break
+                    }
+                    queue.next += 1;
+                    queue.next - 1
+                };
+                // A panic is handed over like a result, otherwise the calling thread would wait for it forever
+                let result = panic::catch_unwind(AssertUnwindSafe(|| work(&items[index])));
+                if sender.send((index, result)).is_err() {
+                    break // Stopped, nothing is listening anymore
+                }
+            });
+        }
+        drop(sender); // The receiver ends once every worker is finished
+
+        let mut waiting = BTreeMap::new();
+        let mut handed_over = 0;
+        for (index, result) in receiver {
+            let result = match result {
+                Ok(result) => result,
+                Err(payload) => {
+                    queue.lock().unwrap().stop = true;
+                    queue_changed.notify_all();
+                    panic::resume_unwind(payload)
+                }
+            };
+            waiting.insert(index, result);
+            while let Some(result) = waiting.remove(&handed_over) {
+                let keep_going = done(handed_over, result);
+                handed_over += 1;
+
+                let mut queue = queue.lock().unwrap();
+                queue.handed_over = handed_over;
+                queue.stop = !keep_going;
+                queue_changed.notify_all();
+                if !keep_going {
+                    return false
+                }
+            }
+        }
+        true
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[test]
+    fn hands_over_in_order() {
+        // Later items finish first
+        let items: Vec<u64> = (0..16).collect();
+        let mut handed_over = Vec::new();
+        let finished = run(&items, 4, |item| {
+            thread::sleep(Duration::from_millis(16 - item));
+            item * 2
+        }, |index, result| {
+            handed_over.push((index, result));
+            true
+        });
+        assert!(finished);
+        assert_eq!(handed_over, items.iter().map(|item| (*item as usize, item * 2)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn stops_when_done_returns_false() {
+        let items: Vec<usize> = (0..1000).collect();
+        let worked = AtomicUsize::new(0);
+        let mut handed_over = 0;
+        let finished = run(&items, 2, |_| {
+            worked.fetch_add(1, Ordering::Relaxed);
+        }, |index, _| {
+            handed_over += 1;
+            index < 5
+        });
+        assert!(!finished);
+        assert_eq!(handed_over, 6);
+        assert!(worked.load(Ordering::Relaxed) < items.len());
+    }
+
+    #[test]
+    fn panics_reach_the_caller() {
+        let items = [1, 2, 3, 4];
+        let result = panic::catch_unwind(|| {
+            run(&items, 2, |item| {
+                if *item == 3 {
+                    panic!("work failed");
+                }
+            }, |_, _| true)
+        });
+        let payload = result.expect_err("The panic should reach the caller");
+        assert_eq!(payload.downcast_ref::<&str>(), Some(&"work failed"));
+    }
+}
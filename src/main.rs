@@ -10,22 +10,24 @@ mod locale;
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use clap::{Parser, ValueEnum};
+use clap::Parser;
 
 // CLI definitions
-#[derive(ValueEnum, Clone, Debug)]
-enum Category {
-    Music,
-    Sounds,
-    Images,
-    Ktx,
-    Rbxm,
-}
+fn parse_category(value: &str) -> Result<String, String> {
+    let value = value.to_lowercase();
+    // Short names for the file tabs
+    let tab = match value.as_str() {
+        "ktx" => "ktx-files".to_owned(),
+        "rbxm" => "rbxm-files".to_owned(),
+        _ => value,
+    };
 
-// Implement `Display` for `Category`
-impl std::fmt::Display for Category {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
+    // Categories come from the signature registry, so user defined categories work too
+    let categories = logic::get_categories();
+    if tab == "music" || categories.contains(&tab) {
+        Ok(tab)
+    } else {
+        Err(format!("expected one of: music, {}", categories.join(", ")))
     }
 }
 
@@ -37,8 +39,8 @@ struct Cli {
     list: bool,
 
     /// Set mode, using this is generally recommended, if this is not provided, the program will run the same function across each mode
-    #[arg(short, long, value_name = "CATEGORY")]
-    mode: Option<Category>,
+    #[arg(short, long, value_name = "CATEGORY", value_parser = parse_category)]
+    mode: Option<String>,
 
     /// Extract asset, extract directory if no asset provided
     #[arg(short, long)]
@@ -52,10 +54,42 @@ struct Cli {
     #[arg(long)]
     extension: bool,
 
+    /// Convert extracted assets to another format, such as png for ktx files
+    #[arg(long, value_name = "FORMAT")]
+    convert: Option<String>,
+
+    /// Only extract one copy of assets that are cached more than once
+    #[arg(long)]
+    unique: bool,
+
+    /// Extract the source of scripts in models, into a folder for each model
+    #[arg(long)]
+    scripts: bool,
+
     /// Define a destination path
     #[arg(short, long)]
     dest: Option<PathBuf>,
 
+    /// Show information about an asset, such as the instance tree of a model
+    #[arg(short, long)]
+    info: Option<String>,
+
+    /// Start a capture, or stop it to list the assets the game downloaded since it started, or extract them with --dest
+    #[arg(long, value_name = "ACTION", value_parser = ["start", "stop"])]
+    capture: Option<String>,
+
+    /// Save a snapshot of the cache under a name, to compare it with another one later
+    #[arg(long, value_name = "NAME")]
+    snapshot: Option<String>,
+
+    /// List the saved snapshots
+    #[arg(long)]
+    snapshots: bool,
+
+    /// Compare two snapshots, prints the assets that were added, removed or modified in each category as JSON
+    #[arg(long, num_args = 2, value_names = ["OLD", "NEW"])]
+    diff: Option<Vec<String>>,
+
     /// Swap two assets
     #[arg(short, long)]
     swap: Option<String>,
@@ -211,36 +245,99 @@ fn configure_fallback_font(ctx: &egui::Context) {
 
 // ======================= Core Functionality Functions =======================
 
-fn get_tab(category: Category) -> String {
-    category.to_string().to_lowercase().replace("ktx","ktx-files").replace("rbxm","rbxm-files")
-}
-
 fn list(tab: String) {
     let cache_directory = logic::get_mode_cache_directory(&tab);
     logic::refresh(cache_directory, tab, true, true); // cli_list_mode is set to true, this will print assets to console
 }
 
-fn extract(tab: String, asset: Option<String>, destination: Option<PathBuf>, add_extension: bool) {
+fn extract(tab: String, asset: Option<String>, destination: Option<PathBuf>, add_extension: bool, convert_to: Option<String>, unique_only: bool) {
+    if let Some(format) = &convert_to {
+        let conversions = logic::get_conversions(&tab);
+        if !conversions.contains(&format.as_str()) {
+            if conversions.is_empty() {
+                eprintln!("Assets in {} can't be converted", tab);
+            } else {
+                eprintln!("Assets in {} can only be converted to: {}", tab, conversions.join(", "));
+            }
+            return
+        }
+    }
+
     let cache_directory = logic::get_mode_cache_directory(&tab);
     if let Some(asset) = asset {
         let dest = destination.unwrap_or_else(|| asset.clone().into());
-        logic::extract_file(cache_directory.join(asset), &tab, dest, add_extension);
+        if let Err(e) = logic::extract_file(cache_directory.join(&asset), &tab, dest, add_extension, convert_to.as_deref()) {
+            eprintln!("Failed to extract {}: {}", asset, e);
+        }
     } else {
         if let Some(dest) = destination {
             logic::refresh(cache_directory.clone(), tab.clone(), true, true);
-            logic::extract_dir(cache_directory, dest, tab, true, false);
+            logic::extract_dir(cache_directory, dest, tab, true, false, convert_to, unique_only);
         } else {
             eprintln!("Please provide either a destination path or an asset to extract! --help for more details.")
         }
     }
 }
 
+fn info(tab: String, asset: String) {
+    let cache_directory = logic::get_mode_cache_directory(&tab);
+    match tab.as_str() {
+        "rbxm-files" => match logic::read_model(cache_directory.join(asset)) {
+            Ok(model) => print!("{}", model.format_tree()),
+            Err(e) => eprintln!("Failed to read model: {}", e),
+        },
+        "sounds" | "music" => match logic::read_audio(cache_directory.join(asset), &tab) {
+            Some(audio) => {
+                println!("Codec: {}", audio.codec);
+                if let Some(duration) = audio.duration {
+                    println!("Duration: {:.3} seconds", duration.as_secs_f64());
+                }
+                println!("Sample rate: {} Hz", audio.sample_rate);
+                println!("Channels: {}", audio.channels);
+                if let Some(bitrate) = audio.bitrate {
+                    println!("Bitrate: {} kbps", bitrate / 1000);
+                }
+            }
+            None => eprintln!("Failed to read the sound, only Ogg Vorbis, Opus and MP3 are supported"),
+        },
+        "images" | "ktx-files" => match logic::read_image(cache_directory.join(asset), &tab) {
+            Some(image) => {
+                println!("Dimensions: {}x{}", image.width, image.height);
+                println!("Format: {}", image.format);
+                println!("Alpha: {}", if image.alpha { "yes" } else { "no" });
+            }
+            None => eprintln!("Failed to read the image, only PNG, WebP, KTX and KTX2 are supported"),
+        },
+        _ => eprintln!("--info is not available for {}", tab),
+    }
+}
+
+fn capture(action: &str, destination: Option<PathBuf>, unique_only: bool) {
+    if action == "start" {
+        logic::start_capture();
+        println!("Capture started, run --capture stop when you are done playing");
+    } else if !logic::stop_capture() {
+        eprintln!("No capture is running, start one with --capture start");
+    } else if let Some(destination) = destination {
+        logic::extract_captured(destination, true, config::get_config_bool("use_alias").unwrap_or(false), unique_only);
+    } else {
+        // List what was captured in every category
+        for category in std::iter::once("music".to_owned()).chain(logic::get_categories()) {
+            let cache_directory = logic::get_mode_cache_directory(&category);
+            logic::refresh(cache_directory, category.clone(), false, true);
+            for asset in logic::get_captured_file_list(&category).unwrap_or_default() {
+                println!("{}: {}", category, asset.name);
+            }
+        }
+    }
+}
+
 fn main() {
     let args = Cli::parse();
 
     if args.list {
         if let Some(category) = args.mode {
-            list(get_tab(category));
+            list(category);
         } else {
             // Not enough arguments - go through all categories
             for category in logic::get_categories() {
@@ -249,18 +346,45 @@ fn main() {
         }
     } else if args.extract.is_some() || args.extract_all {
         if let Some(category) = args.mode {
-            extract(get_tab(category), args.extract, args.dest, args.extension);
+            extract(category, args.extract, args.dest, args.extension, args.convert, args.unique);
         } else {
             // Not enough arguments - go through all categories
             if let Some(destination) = args.dest {
-                logic::extract_all(destination, true, false);
+                logic::extract_all(destination, true, false, args.unique);
             } else {
                 eprintln!("--dest is required to extract all assets. --help for more details")
             }
         }
+    } else if args.scripts {
+        if let Some(dest) = args.dest {
+            let cache_directory = logic::get_mode_cache_directory("rbxm-files");
+            logic::refresh(cache_directory.clone(), "rbxm-files".to_owned(), false, true);
+            logic::extract_scripts(cache_directory, dest, true);
+        } else {
+            eprintln!("--dest is required to extract scripts, --help for more details")
+        }
+    } else if let Some(asset) = args.info {
+        info(args.mode.unwrap_or("rbxm-files".to_owned()), asset);
+    } else if let Some(action) = args.capture {
+        capture(&action, args.dest, args.unique);
+    } else if let Some(name) = args.snapshot {
+        logic::save_snapshot(name, true);
+        println!("{}", logic::get_status());
+    } else if args.snapshots {
+        for name in logic::get_snapshot_names() {
+            println!("{}", name);
+        }
+    } else if let Some(names) = args.diff {
+        match logic::diff_snapshots(&names[0], &names[1]) {
+            Ok(diff) => match serde_json::to_string_pretty(&diff) {
+                Ok(json) => println!("{}", json),
+                Err(e) => eprintln!("Failed to write the diff: {}", e),
+            },
+            Err(e) => eprintln!("{}", e),
+        }
     } else if let Some(asset) = args.swap {
         if let Some(dest) = args.dest {
-            let dir = logic::get_mode_cache_directory(&get_tab(args.mode.unwrap_or(Category::Images)));
+            let dir = logic::get_mode_cache_directory(&args.mode.unwrap_or("images".to_owned()));
             logic::swap_assets(dir, &asset, &dest.to_string_lossy().to_string());
         } else {
             eprintln!("--dest is required for swapping assets, --help for more details")